@@ -0,0 +1,39 @@
+use pawdirecte::api::{LoginManager, Session};
+use std::env::var;
+use tokio::{fs::File, io::AsyncWriteExt};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+  dotenvy::from_path_override("examples/.env")?;
+
+  let mut login = LoginManager::from_access_token(
+    var("USERNAME")?,
+    var("ACCESS_TOKEN")?,
+    var("KIND")?.into(),
+    var("DEVICE_UUID")?,
+  );
+
+  login.request().await?;
+  let session = Session::new(login)?;
+
+  for mut student in session.student_handles() {
+    let documents = student.documents(None).await?;
+
+    // Archive every report card, written as it's being downloaded.
+    for report_card in &documents.report_cards {
+      let mut download = student.download_document(report_card).await?;
+      let name = download
+        .file_name()
+        .unwrap_or_else(|| format!("{}.pdf", report_card.id));
+
+      let mut file = File::create(&name).await?;
+      while let Some(chunk) = download.chunk().await? {
+        file.write_all(&chunk).await?;
+      }
+
+      println!("{} saved to {name}", report_card.name);
+    }
+  }
+
+  Ok(())
+}
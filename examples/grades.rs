@@ -0,0 +1,38 @@
+use pawdirecte::api::{LoginManager, Session};
+use std::env::var;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+  dotenvy::from_path_override("examples/.env")?;
+
+  let mut login = LoginManager::from_access_token(
+    var("USERNAME")?,
+    var("ACCESS_TOKEN")?,
+    var("KIND")?.into(),
+    var("DEVICE_UUID")?,
+  );
+
+  login.request().await?;
+
+  // Parent accounts give a handle for each of their children.
+  let session = Session::new(login)?;
+
+  for mut student in session.student_handles() {
+    println!("# {} {}", student.first_name(), student.last_name());
+
+    let grades = student.grades(None).await?;
+
+    for period in grades.periods {
+      println!("{}: {:?}", period.name, period.overview.average);
+    }
+
+    for grade in grades.grades {
+      println!(
+        "[{}] {} - {:?}/{:?}",
+        grade.subject_name, grade.name, grade.value, grade.out_of
+      );
+    }
+  }
+
+  Ok(())
+}
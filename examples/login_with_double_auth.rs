@@ -19,12 +19,15 @@ async fn main() -> anyhow::Result<()> {
     // Retrieve the challenge, contains a question with possible answers.
     let challenge = login.get_2fa_challenge().await?;
 
-    // Display all the possible answers and reply.
-    let answer =
-      Select::new(&challenge.question, challenge.answers).prompt()?;
+    // Display all the possible answers and pick one.
+    let choice =
+      Select::new(challenge.question(), challenge.answers().to_vec())
+        .raw_prompt()?;
 
-    // Send our answer to the server to validate.
-    login.solve_2fa_challenge(answer).await?;
+    // Send our answer to the server, a wrong one is reported right away.
+    login
+      .solve_2fa_challenge(challenge.answer(choice.index)?)
+      .await?;
 
     // Run a new login attempt.
     login.request().await?;
@@ -36,13 +39,11 @@ async fn main() -> anyhow::Result<()> {
 
   // --------------------------------------------------------------------------
   // If you're interested in re-authenticating without re-using the password,
-  // here's some information that will be useful for the `relogin.rs` example!
-  let account = accounts.first().unwrap();
-  let auth = login.authentication.lock().unwrap();
-  println!("USERNAME={}", account.username);
-  println!("KIND={}", account.kind);
-  println!("ACCESS_TOKEN={}", account.access_token);
-  println!("DEVICE_UUID={}", auth.device_uuid);
+  // save the state of the login, it's what the `relogin.rs` example reads.
+  let state = serde_json::to_string_pretty(&login.export_state())?;
+  std::fs::write("examples/session.json", state)?;
+
+  println!("Logged in as {}", accounts.first().unwrap().username);
 
   Ok(())
 }
@@ -1,29 +1,25 @@
-use std::env::var;
+use pawdirecte::api::LoginManager;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-  dotenvy::from_path_override("examples/.env")?;
+  // Saved by the `login_with_double_auth.rs` example.
+  let state = std::fs::read_to_string("examples/session.json")?;
+  let mut login = LoginManager::from_state(serde_json::from_str(&state)?)?;
 
-  let mut login = pawdirecte::api::LoginManager::from_access_token(
-    var("USERNAME")?,
-    var("ACCESS_TOKEN")?,
-    var("KIND")?,
-    var("DEVICE_UUID")?,
-  );
-
-  // Attempt a login, you might not have to handle double auth
-  // since using access token will bypass this step for you.
+  // Attempt a login, you don't have to handle double auth
+  // since the saved access token will bypass this step for you.
   login.request().await?;
 
   // You can now use the accounts,
   // please see the other examples to see what you can do.
   let accounts = login.accounts()?;
+  println!("Logged in as {}", accounts.first().unwrap().username);
 
   // --------------------------------------------------------------------------
   // A new access token has been generated while doing this operation,
-  // make sure to save this!
-  let account = accounts.first().unwrap();
-  println!("ACCESS_TOKEN={}", account.access_token);
+  // make sure to save the state again!
+  let state = serde_json::to_string_pretty(&login.export_state())?;
+  std::fs::write("examples/session.json", state)?;
 
   Ok(())
 }
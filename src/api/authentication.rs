@@ -1,11 +1,18 @@
-#[derive(Debug, Clone)]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{api::REDACTED, definitions::models::AccountKind};
+
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Authentication {
   pub token: Option<String>,
   pub username: String,
+  /// Never saved, the access token is used to log in again instead.
+  #[serde(skip, default = "unknown_password")]
   pub password: String,
   pub device_uuid: String,
   pub access_token: Option<String>,
-  pub kind: Option<String>,
+  pub kind: Option<AccountKind>,
 }
 
 impl Authentication {
@@ -30,7 +37,7 @@ impl Authentication {
   pub fn from_access_token(
     username: String,
     access_token: String,
-    kind: String,
+    kind: AccountKind,
     device_uuid: String,
   ) -> Self {
     Self {
@@ -42,4 +49,30 @@ impl Authentication {
       kind: Some(kind),
     }
   }
+
+  /// Whether the password is known, it's not when logging in
+  /// with an access token or from a saved state.
+  pub fn has_password(&self) -> bool {
+    self.password != "???"
+  }
+}
+
+impl fmt::Debug for Authentication {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Authentication")
+      .field("token", &self.token.as_ref().map(|_| REDACTED))
+      .field("username", &self.username)
+      .field("password", &REDACTED)
+      .field("device_uuid", &self.device_uuid)
+      .field(
+        "access_token",
+        &self.access_token.as_ref().map(|_| REDACTED),
+      )
+      .field("kind", &self.kind)
+      .finish()
+  }
+}
+
+fn unknown_password() -> String {
+  "???".into()
 }
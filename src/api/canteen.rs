@@ -0,0 +1,187 @@
+use crate::{
+  api::{Error, RequestBuilder, RequestManager},
+  definitions::{
+    models::{MealPass, MealPassOrder, MealReservation},
+    requests::{
+      EmptyRequest, MealPassOrderRequest, ReservationChangeRequest,
+      ReservationsRequest,
+    },
+    responses::{MealPassOrdersResponse, ReservationsResponse},
+  },
+};
+
+use chrono::{Days, NaiveDate};
+use http::Method;
+use serde::de::IgnoredAny;
+
+/// Fetch the canteen reservations of the week starting on `start`.
+pub async fn get_reservations(
+  request_manager: &mut RequestManager,
+  student_id: u32,
+  start: NaiveDate,
+) -> Result<ReservationsResponse, Error> {
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("/v3/eleves/{student_id}/reservations.awp?verbe=get"),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(ReservationsRequest {
+    start_date: start,
+    end_date: start + Days::new(6),
+  })
+  .build()?;
+
+  request_manager.send_data(request).await
+}
+
+/// Reserve, or cancel the reservation of, a meal.
+///
+/// Reservations locked by the school are refused without asking
+/// EcoleDirecte, deadlines are left to it since they are given
+/// in the time zone of the school.
+pub async fn set_meal_reservation(
+  request_manager: &mut RequestManager,
+  student_id: u32,
+  meal: &MealReservation,
+  reserved: bool,
+) -> Result<(), Error> {
+  if meal.is_locked {
+    return Err(Error::ReservationLocked(format!(
+      "{} {}",
+      meal.label, meal.date
+    )));
+  }
+
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("/v3/eleves/{student_id}/reservations.awp?verbe=put"),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(ReservationChangeRequest {
+    date: meal.date,
+    slot: meal.slot.clone(),
+    reserved,
+  })
+  .build()?;
+
+  request_manager.send_data::<IgnoredAny>(request).await?;
+  Ok(())
+}
+
+/// Fetch the meal passes that can be ordered along with past orders.
+pub async fn get_meal_pass_orders(
+  request_manager: &mut RequestManager,
+  student_id: u32,
+) -> Result<MealPassOrdersResponse, Error> {
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("/v3/eleves/{student_id}/commandesPassage.awp?verbe=get"),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(EmptyRequest {})
+  .build()?;
+
+  request_manager.send_data(request).await
+}
+
+/// Order `quantity` of a meal pass, returns the new order.
+pub async fn order_meal_pass(
+  request_manager: &mut RequestManager,
+  student_id: u32,
+  pass: &MealPass,
+  quantity: u32,
+) -> Result<MealPassOrder, Error> {
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("/v3/eleves/{student_id}/commandesPassage.awp?verbe=post"),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(MealPassOrderRequest {
+    pass_id: pass.id,
+    quantity,
+  })
+  .build()?;
+
+  request_manager.send_data(request).await
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{
+    get_meal_pass_orders, get_reservations, order_meal_pass,
+    set_meal_reservation,
+  };
+  use crate::{api::Error, mock};
+  use chrono::NaiveDate;
+  use rust_decimal::Decimal;
+  use serde_json::Value;
+
+  #[tokio::test]
+  async fn can_change_reservation() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let mut request_manager = login.request_manager();
+
+    let monday = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap();
+    let days = get_reservations(&mut request_manager, 4179, monday)
+      .await
+      .unwrap()
+      .days;
+
+    let dinner = &days[1].meals[1];
+    set_meal_reservation(&mut request_manager, 4179, dinner, true)
+      .await
+      .unwrap();
+
+    let request = server.received().pop().unwrap();
+    assert_eq!(request.path, "/v3/eleves/4179/reservations.awp");
+    assert_eq!(request.query["verbe"], "put");
+
+    let form = request.form::<Value>().unwrap();
+    assert_eq!(form["date"], "2025-01-14");
+    assert_eq!(form["code"], "DIN");
+    assert_eq!(form["reserve"], true);
+
+    // locked by the school, nothing is sent.
+    let sent = server.received().len();
+    let lunch = &days[1].meals[0];
+    let result =
+      set_meal_reservation(&mut request_manager, 4179, lunch, true).await;
+
+    assert!(matches!(result, Err(Error::ReservationLocked(_))));
+    assert_eq!(server.received().len(), sent);
+  }
+
+  #[tokio::test]
+  async fn can_order_meal_pass() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let mut request_manager = login.request_manager();
+
+    let passes = get_meal_pass_orders(&mut request_manager, 4179)
+      .await
+      .unwrap()
+      .passes;
+
+    let order = order_meal_pass(&mut request_manager, 4179, &passes[0], 2)
+      .await
+      .unwrap();
+    assert_eq!(order.amount, Decimal::new(4250, 2));
+
+    let request = server.received().pop().unwrap();
+    assert_eq!(request.path, "/v3/eleves/4179/commandesPassage.awp");
+    assert_eq!(request.query["verbe"], "post");
+
+    let form = request.form::<Value>().unwrap();
+    assert_eq!(form["idArticle"], 17);
+    assert_eq!(form["quantite"], 2);
+  }
+}
@@ -0,0 +1,236 @@
+use crate::{
+  api::{Download, Error, RequestBuilder, RequestManager, download_file},
+  definitions::{
+    models::{Account, CloudNode},
+    requests::{
+      CloudDeleteRequest, CloudFolderRequest, CloudNodeRef, EmptyRequest,
+    },
+  },
+};
+
+use http::Method;
+use serde::de::IgnoredAny;
+
+/// Cloud spaces belong to the logged in account.
+fn cloud_path(account: &Account) -> String {
+  format!("/v3/cloud/{}/{}.awp", account.kind.code(), account.id)
+}
+
+/// List the cloud space of the account, starting from its root or
+/// from `folder_id`. Only the first level of folders is loaded, see
+/// [`CloudNode::is_loaded`].
+pub async fn get_cloud_folder(
+  request_manager: &mut RequestManager,
+  account: &Account,
+  folder_id: Option<&str>,
+) -> Result<Vec<CloudNode>, Error> {
+  let mut builder = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("{}?verbe=get", cloud_path(account)),
+  )?
+  .append_version();
+
+  if let Some(folder_id) = folder_id {
+    builder = builder.append_query("idFolder", folder_id);
+  }
+
+  let request = builder
+    .set_token(request_manager.token()?)?
+    .set_form(EmptyRequest {})
+    .build()?;
+
+  request_manager.send_data(request).await
+}
+
+/// Download a file of the cloud space.
+pub async fn download_cloud_file(
+  request_manager: &mut RequestManager,
+  file: &CloudNode,
+) -> Result<Download, Error> {
+  download_file(request_manager, &file.id, "CLOUD").await
+}
+
+/// Upload a file in the folder `folder_id` of the cloud space.
+pub async fn upload_cloud_file(
+  request_manager: &mut RequestManager,
+  folder_id: &str,
+  file_name: &str,
+  content: Vec<u8>,
+) -> Result<(), Error> {
+  let request = RequestBuilder::<()>::new(
+    request_manager.api_url(),
+    Method::POST,
+    "/v3/televersement.awp?verbe=post&mode=CLOUD",
+  )?
+  .append_query("dest", folder_id)
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_file(file_name, content)
+  .build()?;
+
+  request_manager.send_data::<IgnoredAny>(request).await?;
+  Ok(())
+}
+
+/// Create a folder named `name` in the folder `parent`,
+/// returns the new folder.
+pub async fn create_cloud_folder(
+  request_manager: &mut RequestManager,
+  account: &Account,
+  parent: &CloudNode,
+  name: String,
+) -> Result<CloudNode, Error> {
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("{}?verbe=post", cloud_path(account)),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(CloudFolderRequest {
+    parent: node_ref(parent),
+    name,
+  })
+  .build()?;
+
+  request_manager.send_data(request).await
+}
+
+/// Delete files and folders, along with the content of the folders.
+pub async fn delete_cloud_nodes(
+  request_manager: &mut RequestManager,
+  account: &Account,
+  nodes: &[CloudNode],
+) -> Result<(), Error> {
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("{}?verbe=delete", cloud_path(account)),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(CloudDeleteRequest {
+    nodes: nodes.iter().map(node_ref).collect(),
+  })
+  .build()?;
+
+  request_manager.send_data::<IgnoredAny>(request).await?;
+  Ok(())
+}
+
+fn node_ref(node: &CloudNode) -> CloudNodeRef {
+  CloudNodeRef {
+    id: node.id.clone(),
+    kind: node.kind.clone(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{delete_cloud_nodes, download_cloud_file, upload_cloud_file};
+  use crate::{definitions::models::CloudNode, mock};
+  use serde_json::json;
+
+  fn file() -> CloudNode {
+    serde_json::from_value(json!({
+      "type": "file",
+      "libelle": "expose-histoire.pdf",
+      "id": "\\E\\4179\\expose-histoire.pdf",
+    }))
+    .unwrap()
+  }
+
+  #[tokio::test]
+  async fn can_upload_file() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let mut request_manager = login.request_manager();
+
+    upload_cloud_file(
+      &mut request_manager,
+      "\\E\\4179\\Devoirs",
+      "rapport\r\nContent-Type: text/html.pdf",
+      b"%PDF-1.4".to_vec(),
+    )
+    .await
+    .unwrap();
+
+    let request = server.received().pop().unwrap();
+    assert_eq!(request.path, "/v3/televersement.awp");
+    assert_eq!(request.query["dest"], "\\E\\4179\\Devoirs");
+    assert!(request.headers["content-type"].starts_with("multipart/form-data"));
+    assert!(request.body.contains("name=\"file\""));
+    assert!(request.body.contains("%PDF-1.4"));
+
+    // the file name can't add headers to its part.
+    assert!(!request.body.contains("\r\nContent-Type: text/html"));
+  }
+
+  #[tokio::test]
+  async fn can_download_file() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let mut request_manager = login.request_manager();
+
+    let download = download_cloud_file(&mut request_manager, &file())
+      .await
+      .unwrap();
+
+    assert_eq!(download.file_name().as_deref(), Some("expose-histoire.pdf"));
+    assert_eq!(download.bytes().await.unwrap(), mock::FILE_CONTENT);
+
+    let request = server.received().pop().unwrap();
+    assert_eq!(request.path, "/v3/telechargement.awp");
+    assert_eq!(request.query["leTypeDeFichier"], "CLOUD");
+  }
+
+  #[tokio::test]
+  async fn renews_expired_token_to_download() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let mut request_manager = login.request_manager();
+
+    server.expire_tokens();
+    let download = download_cloud_file(&mut request_manager, &file())
+      .await
+      .unwrap();
+
+    assert_eq!(download.bytes().await.unwrap(), mock::FILE_CONTENT);
+
+    let paths = server
+      .received()
+      .into_iter()
+      .skip(2)
+      .map(|request| request.path)
+      .collect::<Vec<_>>();
+    assert_eq!(
+      paths,
+      [
+        "/v3/telechargement.awp",
+        "/v3/login.awp",
+        "/v3/login.awp",
+        "/v3/telechargement.awp"
+      ]
+    );
+  }
+
+  #[tokio::test]
+  async fn can_delete_nodes() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let account = &login.accounts().unwrap()[0];
+    let mut request_manager = login.request_manager();
+
+    delete_cloud_nodes(&mut request_manager, account, &[file()])
+      .await
+      .unwrap();
+
+    let request = server.received().pop().unwrap();
+    assert_eq!(request.path, "/v3/cloud/E/4179.awp");
+    assert_eq!(request.query["verbe"], "delete");
+
+    let form = request.form::<serde_json::Value>().unwrap();
+    assert_eq!(form["tabNodes"][0]["id"], "\\E\\4179\\expose-histoire.pdf");
+  }
+}
@@ -8,3 +8,6 @@ pub const API_URL: &str = "https://api.ecoledirecte.com";
 
 pub const API_VERSION: &str = "7.2.3";
 pub const USER_AGENT: &str = formatcp!("Android EDMOBILE v{API_VERSION}");
+
+/// Printed instead of secrets by `Debug` implementations.
+pub(crate) const REDACTED: &str = "<redacted>";
@@ -0,0 +1,408 @@
+use aes_gcm::{
+  Aes256Gcm, Key, KeyInit, Nonce,
+  aead::{Aead, AeadCore, OsRng, rand_core::RngCore},
+};
+use serde::{Deserialize, Serialize};
+use serde_with::{base64::Base64, serde_as};
+use sha2::Sha256;
+use std::{
+  collections::HashMap, fmt, fs, ops::RangeInclusive, path::PathBuf,
+  sync::Mutex,
+};
+
+use crate::{
+  api::{Authentication, Error, REDACTED},
+  definitions::{models::AccountKind, responses::DoubleAuthSolveResponse},
+};
+
+/// Secrets needed to log in again, either with the password
+/// or with the access token of a previous login.
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+pub struct Credentials {
+  pub username: String,
+  pub password: Option<String>,
+  pub device_uuid: String,
+  pub access_token: Option<String>,
+  pub kind: Option<AccountKind>,
+  /// Skips the double auth when logging in with the password.
+  #[serde(default)]
+  pub trusted_device: Option<DoubleAuthSolveResponse>,
+}
+
+impl From<&Authentication> for Credentials {
+  fn from(auth: &Authentication) -> Self {
+    let password = Some(auth.password.clone()).filter(|_| auth.has_password());
+
+    Self {
+      username: auth.username.clone(),
+      password,
+      device_uuid: auth.device_uuid.clone(),
+      access_token: auth.access_token.clone(),
+      kind: auth.kind.clone(),
+      trusted_device: None,
+    }
+  }
+}
+
+impl From<Credentials> for Authentication {
+  fn from(credentials: Credentials) -> Self {
+    Self {
+      token: None,
+      username: credentials.username,
+      password: credentials.password.unwrap_or_else(|| "???".into()),
+      device_uuid: credentials.device_uuid,
+      access_token: credentials.access_token,
+      kind: credentials.kind,
+    }
+  }
+}
+
+impl fmt::Debug for Credentials {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Credentials")
+      .field("username", &self.username)
+      .field("password", &self.password.as_ref().map(|_| REDACTED))
+      .field("device_uuid", &self.device_uuid)
+      .field(
+        "access_token",
+        &self.access_token.as_ref().map(|_| REDACTED),
+      )
+      .field("kind", &self.kind)
+      .field("trusted_device", &self.trusted_device)
+      .finish()
+  }
+}
+
+/// Where [`LoginManager`](crate::api::LoginManager) keeps credentials,
+/// it's updated after every successful login since access tokens rotate.
+pub trait CredentialStore: fmt::Debug + Send + Sync {
+  fn load(&self, username: &str) -> Result<Option<Credentials>, Error>;
+  fn save(&self, credentials: &Credentials) -> Result<(), Error>;
+  fn remove(&self, username: &str) -> Result<(), Error>;
+}
+
+/// Keeps credentials for the lifetime of the process only.
+#[derive(Default)]
+pub struct MemoryCredentialStore {
+  credentials: Mutex<HashMap<String, Credentials>>,
+}
+
+impl MemoryCredentialStore {
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+impl fmt::Debug for MemoryCredentialStore {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let credentials = self.credentials.lock().unwrap();
+
+    f.debug_struct("MemoryCredentialStore")
+      .field("usernames", &credentials.keys().collect::<Vec<_>>())
+      .finish()
+  }
+}
+
+impl CredentialStore for MemoryCredentialStore {
+  fn load(&self, username: &str) -> Result<Option<Credentials>, Error> {
+    let credentials = self.credentials.lock().unwrap();
+    Ok(credentials.get(username).cloned())
+  }
+
+  fn save(&self, credentials: &Credentials) -> Result<(), Error> {
+    let mut stored = self.credentials.lock().unwrap();
+    stored.insert(credentials.username.clone(), credentials.clone());
+    Ok(())
+  }
+
+  fn remove(&self, username: &str) -> Result<(), Error> {
+    self.credentials.lock().unwrap().remove(username);
+    Ok(())
+  }
+}
+
+/// PBKDF2 rounds used to derive the key from the passphrase.
+const KEY_DERIVATION_ROUNDS: u32 = 600_000;
+/// Rounds accepted when reading a file, fewer would weaken the key
+/// and more would keep the process busy for minutes.
+const ACCEPTED_ROUNDS: RangeInclusive<u32> = 100_000..=10_000_000;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+/// Layout of the file written by [`FileCredentialStore`].
+#[serde_as]
+#[derive(Deserialize, Serialize)]
+struct EncryptedFile {
+  rounds: u32,
+  #[serde_as(as = "Base64")]
+  salt: Vec<u8>,
+  #[serde_as(as = "Base64")]
+  nonce: Vec<u8>,
+  /// Every [`Credentials`] by username, encrypted with AES-256-GCM.
+  #[serde_as(as = "Base64")]
+  data: Vec<u8>,
+}
+
+/// Keeps credentials in a file, encrypted under a passphrase.
+pub struct FileCredentialStore {
+  path: PathBuf,
+  passphrase: String,
+  rounds: u32,
+  // Saving reads the file before writing it back, one at a time.
+  lock: Mutex<()>,
+}
+
+impl FileCredentialStore {
+  /// The file is created on the first save.
+  pub fn new(path: impl Into<PathBuf>, passphrase: impl Into<String>) -> Self {
+    Self {
+      path: path.into(),
+      passphrase: passphrase.into(),
+      rounds: KEY_DERIVATION_ROUNDS,
+      lock: Mutex::new(()),
+    }
+  }
+
+  /// Derive the key with the fewest accepted rounds, to keep tests fast.
+  #[cfg(test)]
+  fn with_min_rounds(mut self) -> Self {
+    self.rounds = *ACCEPTED_ROUNDS.start();
+    self
+  }
+
+  fn cipher(&self, salt: &[u8], rounds: u32) -> Aes256Gcm {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(
+      self.passphrase.as_bytes(),
+      salt,
+      rounds,
+      &mut key,
+    );
+
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+  }
+
+  fn read(&self) -> Result<HashMap<String, Credentials>, Error> {
+    if !self.path.exists() {
+      return Ok(HashMap::new());
+    }
+
+    let file: EncryptedFile = serde_json::from_slice(&fs::read(&self.path)?)?;
+
+    if !ACCEPTED_ROUNDS.contains(&file.rounds)
+      || file.salt.len() != SALT_LENGTH
+      || file.nonce.len() != NONCE_LENGTH
+    {
+      return Err(Error::CredentialCrypto());
+    }
+
+    let data = self
+      .cipher(&file.salt, file.rounds)
+      .decrypt(Nonce::from_slice(&file.nonce), file.data.as_ref())
+      .map_err(|_| Error::CredentialCrypto())?;
+
+    Ok(serde_json::from_slice(&data)?)
+  }
+
+  fn write(
+    &self,
+    credentials: &HashMap<String, Credentials>,
+  ) -> Result<(), Error> {
+    let mut salt = vec![0u8; SALT_LENGTH];
+    OsRng.fill_bytes(&mut salt);
+
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let data = self
+      .cipher(&salt, self.rounds)
+      .encrypt(&nonce, serde_json::to_vec(credentials)?.as_ref())
+      .map_err(|_| Error::CredentialCrypto())?;
+
+    let file = EncryptedFile {
+      rounds: self.rounds,
+      salt,
+      nonce: nonce.to_vec(),
+      data,
+    };
+
+    // written aside then moved over the file, so a failed write
+    // leaves the previous credentials untouched.
+    let mut temporary = self.path.clone().into_os_string();
+    temporary.push(".tmp");
+
+    fs::write(&temporary, serde_json::to_vec(&file)?)?;
+    fs::rename(&temporary, &self.path)?;
+    Ok(())
+  }
+}
+
+impl fmt::Debug for FileCredentialStore {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("FileCredentialStore")
+      .field("path", &self.path)
+      .field("passphrase", &REDACTED)
+      .finish()
+  }
+}
+
+impl CredentialStore for FileCredentialStore {
+  fn load(&self, username: &str) -> Result<Option<Credentials>, Error> {
+    let _lock = self.lock.lock().unwrap();
+    Ok(self.read()?.remove(username))
+  }
+
+  fn save(&self, credentials: &Credentials) -> Result<(), Error> {
+    let _lock = self.lock.lock().unwrap();
+
+    let mut stored = self.read()?;
+    stored.insert(credentials.username.clone(), credentials.clone());
+    self.write(&stored)
+  }
+
+  fn remove(&self, username: &str) -> Result<(), Error> {
+    let _lock = self.lock.lock().unwrap();
+
+    let mut stored = self.read()?;
+    if stored.remove(username).is_some() {
+      self.write(&stored)?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{
+    CredentialStore, Credentials, FileCredentialStore, MemoryCredentialStore,
+  };
+  use crate::{api::Error, definitions::models::AccountKind};
+  use serde_json::Value;
+  use std::{fs, path::PathBuf};
+
+  fn credentials() -> Credentials {
+    Credentials {
+      username: "EDELEVE".into(),
+      password: Some("pawdirecte".into()),
+      device_uuid: "device".into(),
+      access_token: Some("access-token".into()),
+      kind: Some(AccountKind::Student),
+      trusted_device: None,
+    }
+  }
+
+  #[test]
+  fn memory_store_keeps_credentials() {
+    let store = MemoryCredentialStore::new();
+    store.save(&credentials()).unwrap();
+
+    assert_eq!(store.load("EDELEVE").unwrap(), Some(credentials()));
+
+    store.remove("EDELEVE").unwrap();
+    assert_eq!(store.load("EDELEVE").unwrap(), None);
+  }
+
+  fn temporary_path() -> PathBuf {
+    std::env::temp_dir()
+      .join(format!("pawdirecte-{}.json", uuid::Uuid::new_v4()))
+  }
+
+  #[test]
+  fn file_store_encrypts_credentials() {
+    let path = temporary_path();
+
+    let store =
+      FileCredentialStore::new(&path, "correct horse").with_min_rounds();
+    store.save(&credentials()).unwrap();
+
+    let file = fs::read_to_string(&path).unwrap();
+    assert!(!file.contains("pawdirecte"));
+    assert!(!file.contains("access-token"));
+
+    // the rounds are read from the file.
+    let store = FileCredentialStore::new(&path, "correct horse");
+    assert_eq!(store.load("EDELEVE").unwrap(), Some(credentials()));
+
+    let store = FileCredentialStore::new(&path, "wrong horse");
+    let result = store.load("EDELEVE");
+    assert!(matches!(result, Err(Error::CredentialCrypto())));
+
+    fs::remove_file(path).unwrap();
+  }
+
+  #[test]
+  fn file_store_derives_key_with_600_000_rounds() {
+    let path = temporary_path();
+
+    let store = FileCredentialStore::new(&path, "correct horse");
+    store.save(&credentials()).unwrap();
+
+    let file: Value =
+      serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+    assert_eq!(file["rounds"], 600_000);
+
+    fs::remove_file(path).unwrap();
+  }
+
+  #[test]
+  fn file_store_refuses_tampered_files() {
+    let path = temporary_path();
+
+    let store =
+      FileCredentialStore::new(&path, "correct horse").with_min_rounds();
+    store.save(&credentials()).unwrap();
+    let file: Value =
+      serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+
+    let tampered = [
+      ("nonce", Value::from("AAAA")),
+      ("salt", Value::from("")),
+      ("rounds", Value::from(1)),
+      ("rounds", Value::from(u32::MAX)),
+    ];
+
+    for (field, value) in tampered {
+      let mut file = file.clone();
+      file[field] = value;
+      fs::write(&path, file.to_string()).unwrap();
+
+      let result = store.load("EDELEVE");
+      assert!(
+        matches!(result, Err(Error::CredentialCrypto())),
+        "{field} has been accepted"
+      );
+    }
+
+    fs::remove_file(path).unwrap();
+  }
+
+  #[test]
+  fn file_store_replaces_the_file_at_once() {
+    let path = temporary_path();
+
+    let store =
+      FileCredentialStore::new(&path, "correct horse").with_min_rounds();
+    store.save(&credentials()).unwrap();
+
+    let mut other = credentials();
+    other.username = "EDFAMILLE".into();
+    store.save(&other).unwrap();
+
+    let mut temporary = path.clone().into_os_string();
+    temporary.push(".tmp");
+    assert!(!PathBuf::from(temporary).exists());
+
+    assert_eq!(store.load("EDELEVE").unwrap(), Some(credentials()));
+    assert_eq!(store.load("EDFAMILLE").unwrap(), Some(other));
+
+    fs::remove_file(path).unwrap();
+  }
+
+  #[test]
+  fn debug_output_hides_secrets() {
+    let output = format!("{:?}", credentials());
+
+    assert!(output.contains("EDELEVE"));
+    assert!(!output.contains("pawdirecte"));
+    assert!(!output.contains("access-token"));
+  }
+}
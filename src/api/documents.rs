@@ -0,0 +1,104 @@
+use crate::{
+  api::{Download, Error, RequestBuilder, RequestManager, download_file},
+  definitions::{
+    models::Document, requests::DocumentsRequest, responses::DocumentsResponse,
+  },
+};
+
+use http::Method;
+
+/// List the documents of a student: report cards, certificates, ...
+///
+/// `school_year` is `None` for the current year, otherwise `"YYYY-YYYY"`
+/// among [`DocumentsParams::archived_years`].
+///
+/// [`DocumentsParams::archived_years`]: crate::definitions::models::DocumentsParams::archived_years
+pub async fn get_documents(
+  request_manager: &mut RequestManager,
+  student_id: u32,
+  school_year: Option<String>,
+) -> Result<DocumentsResponse, Error> {
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    "/v3/elevesDocuments.awp?verbe=get",
+  )?
+  .append_query("idEleve", &student_id.to_string())
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(DocumentsRequest {
+    school_year: school_year.unwrap_or_default(),
+  })
+  .build()?;
+
+  request_manager.send_data(request).await
+}
+
+/// Download a document, read it from the returned [`Download`].
+pub async fn download_document(
+  request_manager: &mut RequestManager,
+  document: &Document,
+) -> Result<Download, Error> {
+  download_file(request_manager, &document.id.to_string(), &document.kind).await
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{download_document, get_documents};
+  use crate::mock;
+  use serde_json::Value;
+
+  #[tokio::test]
+  async fn can_get_documents() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let mut request_manager = login.request_manager();
+
+    let documents =
+      get_documents(&mut request_manager, 4179, Some("2023-2024".into()))
+        .await
+        .unwrap();
+    assert_eq!(documents.report_cards.len(), 2);
+
+    let request = server.received().pop().unwrap();
+    assert_eq!(request.path, "/v3/elevesDocuments.awp");
+    assert_eq!(request.query["verbe"], "get");
+    assert_eq!(request.query["idEleve"], "4179");
+
+    let form = request.form::<Value>().unwrap();
+    assert_eq!(form["archive"], "2023-2024");
+
+    get_documents(&mut request_manager, 4179, None)
+      .await
+      .unwrap();
+
+    let form = server.received().pop().unwrap().form::<Value>().unwrap();
+    assert_eq!(form["archive"], "");
+  }
+
+  #[tokio::test]
+  async fn can_download_report_card() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let mut request_manager = login.request_manager();
+
+    let documents = get_documents(&mut request_manager, 4179, None)
+      .await
+      .unwrap();
+
+    let download =
+      download_document(&mut request_manager, &documents.report_cards[0])
+        .await
+        .unwrap();
+    assert_eq!(download.file_name().as_deref(), Some("5531"));
+    assert_eq!(download.bytes().await.unwrap(), mock::FILE_CONTENT);
+
+    let request = server.received().pop().unwrap();
+    assert_eq!(request.path, "/v3/telechargement.awp");
+    assert_eq!(request.query["fichierId"], "5531");
+    assert_eq!(request.query["leTypeDeFichier"], "Note");
+
+    let form = request.form::<Value>().unwrap();
+    assert_eq!(form["forceDownload"], 0);
+  }
+}
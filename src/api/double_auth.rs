@@ -0,0 +1,90 @@
+use crate::{api::Error, definitions::responses::DoubleAuthChallengeResponse};
+
+/// Question asked when logging in from an unknown device,
+/// it has to be answered with one of its propositions.
+#[derive(Debug, Clone)]
+pub struct DoubleAuthChallenge {
+  question: String,
+  answers: Vec<String>,
+}
+
+/// One of the propositions of a [`DoubleAuthChallenge`],
+/// ready to be sent with [`LoginManager::solve_2fa_challenge`].
+///
+/// [`LoginManager::solve_2fa_challenge`]: crate::api::LoginManager::solve_2fa_challenge
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoubleAuthAnswer(String);
+
+impl DoubleAuthChallenge {
+  pub fn question(&self) -> &str {
+    &self.question
+  }
+
+  /// Propositions, only one of them is right.
+  pub fn answers(&self) -> &[String] {
+    &self.answers
+  }
+
+  /// Pick the proposition at `index` of [`DoubleAuthChallenge::answers`].
+  pub fn answer(&self, index: usize) -> Result<DoubleAuthAnswer, Error> {
+    self
+      .answers
+      .get(index)
+      .map(|answer| DoubleAuthAnswer(answer.clone()))
+      .ok_or_else(|| Error::InvalidDoubleAuthAnswer(index.to_string()))
+  }
+
+  /// Pick the proposition matching `answer`.
+  pub fn choose(&self, answer: &str) -> Result<DoubleAuthAnswer, Error> {
+    self
+      .answers
+      .iter()
+      .find(|proposition| proposition.as_str() == answer)
+      .map(|answer| DoubleAuthAnswer(answer.clone()))
+      .ok_or_else(|| Error::InvalidDoubleAuthAnswer(answer.into()))
+  }
+}
+
+impl From<DoubleAuthChallengeResponse> for DoubleAuthChallenge {
+  fn from(response: DoubleAuthChallengeResponse) -> Self {
+    Self {
+      question: response.question,
+      answers: response.answers,
+    }
+  }
+}
+
+impl DoubleAuthAnswer {
+  pub fn as_str(&self) -> &str {
+    &self.0
+  }
+}
+
+impl From<DoubleAuthAnswer> for String {
+  fn from(answer: DoubleAuthAnswer) -> Self {
+    answer.0
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::DoubleAuthChallenge;
+  use crate::api::Error;
+
+  #[test]
+  fn only_accepts_propositions() {
+    let challenge = DoubleAuthChallenge {
+      question: "Quelle est votre année de naissance ?".into(),
+      answers: vec!["2009".into(), "2010".into()],
+    };
+
+    assert_eq!(challenge.answer(1).unwrap().as_str(), "2010");
+    assert_eq!(challenge.choose("2009").unwrap().as_str(), "2009");
+
+    let result = challenge.answer(2);
+    assert!(matches!(result, Err(Error::InvalidDoubleAuthAnswer(_))));
+
+    let result = challenge.choose("1999");
+    assert!(matches!(result, Err(Error::InvalidDoubleAuthAnswer(_))));
+  }
+}
@@ -0,0 +1,139 @@
+use bytes::Bytes;
+use http::Method;
+use percent_encoding::percent_decode_str;
+use reqwest::{Response, header::CONTENT_DISPOSITION};
+
+use crate::{
+  api::{Error, RequestBuilder, RequestManager},
+  definitions::requests::DownloadRequest,
+};
+
+/// File being downloaded, its content is read chunk by chunk
+/// so large files don't have to be kept in memory.
+#[derive(Debug)]
+pub struct Download {
+  response: Response,
+}
+
+impl Download {
+  pub(crate) fn new(response: Response) -> Self {
+    Self { response }
+  }
+
+  /// Size of the file in bytes, when given by EcoleDirecte.
+  pub fn size(&self) -> Option<u64> {
+    self.response.content_length()
+  }
+
+  /// Name of the file, from the `Content-Disposition` header.
+  ///
+  /// Only the last component of the name is kept, so it can't point
+  /// outside of the folder the file is saved to.
+  pub fn file_name(&self) -> Option<String> {
+    let header = self.response.headers().get(CONTENT_DISPOSITION)?;
+    content_disposition_file_name(&String::from_utf8_lossy(header.as_bytes()))
+  }
+
+  /// Next chunk of the file, `None` once it has been entirely read.
+  pub async fn chunk(&mut self) -> Result<Option<Bytes>, Error> {
+    Ok(self.response.chunk().await?)
+  }
+
+  /// Read the whole file at once.
+  pub async fn bytes(self) -> Result<Bytes, Error> {
+    Ok(self.response.bytes().await?)
+  }
+}
+
+/// File name of a `Content-Disposition` header, `filename*=` (RFC 6266)
+/// is preferred over `filename=` since it can hold any character.
+fn content_disposition_file_name(header: &str) -> Option<String> {
+  let mut name = None;
+
+  for part in header.split(';').map(str::trim) {
+    if let Some(value) = part.strip_prefix("filename*=") {
+      // `charset'language'percent-encoded-name`
+      let encoded = value.splitn(3, '\'').nth(2)?;
+      name = Some(percent_decode_str(encoded).decode_utf8_lossy().into());
+      break;
+    }
+
+    if let Some(value) = part.strip_prefix("filename=") {
+      name = Some(value.trim_matches('"').to_string());
+    }
+  }
+
+  sanitize_file_name(&name?)
+}
+
+/// Last component of a file name, without control characters.
+fn sanitize_file_name(name: &str) -> Option<String> {
+  let name = name
+    .rsplit(['/', '\\'])
+    .next()?
+    .chars()
+    .filter(|c| !c.is_control())
+    .collect::<String>();
+
+  match name.trim() {
+    "" | "." | ".." => None,
+    name => Some(name.into()),
+  }
+}
+
+/// Download a file through `telechargement.awp`, `file_kind` tells
+/// where the file comes from such as `CLOUD`.
+pub async fn download_file(
+  request_manager: &mut RequestManager,
+  file_id: &str,
+  file_kind: &str,
+) -> Result<Download, Error> {
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    "/v3/telechargement.awp?verbe=get",
+  )?
+  .append_query("fichierId", file_id)
+  .append_query("leTypeDeFichier", file_kind)
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(DownloadRequest { force_download: 0 })
+  .build()?;
+
+  request_manager.download(request).await
+}
+
+#[cfg(test)]
+mod tests {
+  use super::content_disposition_file_name;
+
+  #[test]
+  fn reads_file_names() {
+    let name = content_disposition_file_name(
+      r#"attachment; filename="bulletin 1er trimestre.pdf""#,
+    );
+    assert_eq!(name.as_deref(), Some("bulletin 1er trimestre.pdf"));
+
+    let name = content_disposition_file_name(
+      "attachment; filename=\"releve.pdf\"; filename*=UTF-8''relev%C3%A9.pdf",
+    );
+    assert_eq!(name.as_deref(), Some("relevé.pdf"));
+  }
+
+  #[test]
+  fn keeps_file_names_in_their_folder() {
+    let name = content_disposition_file_name(r#"attachment; filename="../x""#);
+    assert_eq!(name.as_deref(), Some("x"));
+
+    let name = content_disposition_file_name(
+      r"attachment; filename*=UTF-8''..%5C..%5Cwindows%5Cx.dll",
+    );
+    assert_eq!(name.as_deref(), Some("x.dll"));
+
+    let name = content_disposition_file_name(r#"attachment; filename="..""#);
+    assert_eq!(name, None);
+
+    let name = content_disposition_file_name("attachment; filename=\"a\r\nb\"");
+    assert_eq!(name.as_deref(), Some("ab"));
+  }
+}
@@ -1,20 +1,72 @@
+/// Errors of the crate, more variants can be added by features
+/// such as `barcode` so matching on them needs a wildcard arm.
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
   #[error("unauthenticated session")]
   WrongLoginState(),
-  #[error("identifiers or tokens are incorrect")]
-  BadCredentials(),
-  #[error("a newer version of EcoleDirecte is available")]
-  InvalidVersion(),
-  #[error("establishment is closed, wait until it reopens")]
-  EstablishmentUnavailable(),
+  #[error("identifiers or tokens are incorrect: {0}")]
+  BadCredentials(String),
+  #[error("a newer version of EcoleDirecte is available: {0}")]
+  InvalidVersion(String),
+  #[error("establishment is closed, wait until it reopens: {0}")]
+  EstablishmentUnavailable(String),
+  #[error("token is invalid or has expired: {0}")]
+  TokenExpired(String),
+  #[error("account has been blocked by the establishment: {0}")]
+  AccountBlocked(String),
+  #[error("this feature is disabled for the account: {0}")]
+  ForbiddenModule(String),
+  #[error("too many attempts, wait before trying again: {0}")]
+  TooManyAttempts(String),
+  #[error("a captcha has to be solved on the official website: {0}")]
+  CaptchaRequired(String),
+  #[error("EcoleDirecte is under maintenance: {0}")]
+  Maintenance(String),
+  #[error("\"{0}\" is not one of the double auth propositions")]
+  InvalidDoubleAuthAnswer(String),
+  #[error("double auth answer has been rejected: {0}")]
+  DoubleAuthRejected(String),
+  #[error("accounts of kind \"{0}\" are not supported")]
+  UnsupportedAccountKind(String),
+  #[error("reservation of {0} can't be changed anymore")]
+  ReservationLocked(String),
   #[error("GTK cookie not found, is EcoleDirecte up?")]
   CookieGtkNotFound(),
+  #[error("unexpected response body at `{path}`: {source}")]
+  UnexpectedResponse {
+    /// Location of the faulty value, such as `data.accounts[0].id`.
+    path: String,
+    #[source]
+    source: serde_json::Error,
+  },
+  #[error("token expired and re-authentication failed")]
+  ReauthenticationFailed(#[source] Box<Error>),
+  #[error("logged in, but the credentials could not be saved")]
+  CredentialStoreFailed(#[source] Box<Error>),
+  #[error("session state version {0} is not supported")]
+  UnsupportedSessionState(u32),
+  #[error("no credentials stored for {0}")]
+  MissingCredentials(String),
+  #[error("credentials could not be encrypted or decrypted, wrong passphrase?")]
+  CredentialCrypto(),
+  #[error("EcoleDirecte returned code {code}: {message}")]
+  Api { code: u32, message: String },
+  #[cfg(feature = "barcode")]
+  #[error("\"{0}\" can't be encoded as a barcode")]
+  InvalidBarcode(String),
+  #[cfg(feature = "barcode")]
+  #[error(transparent)]
+  Png(#[from] png::EncodingError),
+  #[error(transparent)]
+  Io(#[from] std::io::Error),
   #[error(transparent)]
   Network(#[from] reqwest::Error),
   #[error(transparent)]
   HeaderValue(#[from] reqwest::header::InvalidHeaderValue),
   #[error(transparent)]
+  HeaderToStr(#[from] reqwest::header::ToStrError),
+  #[error(transparent)]
   Cookie(#[from] cookie_parser::CookieParseError),
   #[error(transparent)]
   JsonEncode(#[from] serde_json::Error),
@@ -23,3 +75,113 @@ pub enum Error {
   #[error(transparent)]
   FormEncode(#[from] serde_urlencoded::ser::Error),
 }
+
+impl Error {
+  /// Error matching a response code of EcoleDirecte along with its message,
+  /// `None` when the code is a successful one.
+  ///
+  /// Codes without a dedicated variant are given as an [`Error::Api`].
+  pub fn from_code(code: u32, message: Option<String>) -> Option<Self> {
+    let message = message.unwrap_or_default();
+
+    Some(match code {
+      // 250 asks for a double authentication, the login handles it.
+      200 | 250 => return None,
+      505 => Self::BadCredentials(message),
+      516 => Self::AccountBlocked(message),
+      517 => Self::InvalidVersion(message),
+      // 520 for an invalid token, 525 for an expired one.
+      520 | 525 => Self::TokenExpired(message),
+      521 => Self::ForbiddenModule(message),
+      522 => Self::TooManyAttempts(message),
+      535 => Self::EstablishmentUnavailable(message),
+      555 => Self::Maintenance(message),
+      560 => Self::CaptchaRequired(message),
+      _ => Self::Api { code, message },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::Error;
+
+  fn from_code(code: u32) -> Option<Error> {
+    Error::from_code(code, Some(format!("message {code}")))
+  }
+
+  #[test]
+  fn successful_codes_are_not_errors() {
+    assert!(from_code(200).is_none());
+    assert!(from_code(250).is_none());
+  }
+
+  #[test]
+  fn maps_bad_credentials() {
+    let error = Error::from_code(505, Some("Mot de passe invalide !".into()));
+    assert_eq!(
+      error.unwrap().to_string(),
+      "identifiers or tokens are incorrect: Mot de passe invalide !"
+    );
+  }
+
+  #[test]
+  fn maps_blocked_accounts() {
+    let error = from_code(516);
+    assert!(
+      matches!(error, Some(Error::AccountBlocked(m)) if m == "message 516")
+    );
+  }
+
+  #[test]
+  fn maps_outdated_versions() {
+    assert!(matches!(from_code(517), Some(Error::InvalidVersion(_))));
+  }
+
+  #[test]
+  fn maps_invalid_and_expired_tokens() {
+    let error = Error::from_code(525, Some("Token expiré !".into()));
+    assert!(
+      matches!(error, Some(Error::TokenExpired(message)) if message == "Token expiré !")
+    );
+
+    assert!(matches!(from_code(520), Some(Error::TokenExpired(_))));
+  }
+
+  #[test]
+  fn maps_forbidden_modules() {
+    assert!(matches!(from_code(521), Some(Error::ForbiddenModule(_))));
+  }
+
+  #[test]
+  fn maps_too_many_attempts() {
+    assert!(matches!(from_code(522), Some(Error::TooManyAttempts(_))));
+  }
+
+  #[test]
+  fn maps_closed_establishments() {
+    assert!(matches!(
+      from_code(535),
+      Some(Error::EstablishmentUnavailable(_))
+    ));
+  }
+
+  #[test]
+  fn maps_maintenance() {
+    assert_eq!(
+      from_code(555).unwrap().to_string(),
+      "EcoleDirecte is under maintenance: message 555"
+    );
+  }
+
+  #[test]
+  fn maps_required_captchas() {
+    assert!(matches!(from_code(560), Some(Error::CaptchaRequired(_))));
+  }
+
+  #[test]
+  fn falls_back_to_api_errors() {
+    let error = Error::from_code(999, None);
+    assert!(matches!(error, Some(Error::Api { code: 999, .. })));
+  }
+}
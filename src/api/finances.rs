@@ -0,0 +1,50 @@
+use crate::{
+  api::{Error, RequestBuilder, RequestManager},
+  definitions::{
+    requests::EmptyRequest, responses::FinancialSituationResponse,
+  },
+};
+
+use http::Method;
+
+/// Fetch the balances of the accounts held by the school, with their
+/// transactions, and the invoices of the family.
+pub async fn get_financial_situation(
+  request_manager: &mut RequestManager,
+) -> Result<FinancialSituationResponse, Error> {
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    "/v3/comptes/detail.awp?verbe=get",
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(EmptyRequest {})
+  .build()?;
+
+  request_manager.send_data(request).await
+}
+
+#[cfg(test)]
+mod tests {
+  use super::get_financial_situation;
+  use crate::mock;
+  use rust_decimal::Decimal;
+  use serde_json::{Value, json};
+
+  #[tokio::test]
+  async fn can_get_financial_situation() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::FAMILY_USERNAME).await.unwrap();
+    let mut request_manager = login.request_manager();
+
+    let situation =
+      get_financial_situation(&mut request_manager).await.unwrap();
+    assert_eq!(situation.accounts[0].balance, Decimal::new(-123450, 2));
+
+    let request = server.received().pop().unwrap();
+    assert_eq!(request.path, "/v3/comptes/detail.awp");
+    assert_eq!(request.query["verbe"], "get");
+    assert_eq!(request.form::<Value>().unwrap(), json!({}));
+  }
+}
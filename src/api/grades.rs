@@ -0,0 +1,29 @@
+use crate::{
+  api::{Error, RequestBuilder, RequestManager},
+  definitions::{requests::GradesRequest, responses::GradesResponse},
+};
+
+use http::Method;
+
+/// Fetch the periods, averages and grades of a student.
+///
+/// `school_year` is `None` for the current year, otherwise `"YYYY-YYYY"`.
+pub async fn get_grades(
+  request_manager: &mut RequestManager,
+  student_id: u32,
+  school_year: Option<String>,
+) -> Result<GradesResponse, Error> {
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("/v3/eleves/{student_id}/notes.awp?verbe=get"),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(GradesRequest {
+    school_year: school_year.unwrap_or_default(),
+  })
+  .build()?;
+
+  request_manager.send_data(request).await
+}
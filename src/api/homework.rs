@@ -0,0 +1,263 @@
+use crate::{
+  api::{Error, RequestBuilder, RequestManager},
+  definitions::{
+    models::HomeworkDay,
+    requests::{EmptyRequest, HomeworkDoneRequest},
+    responses::UpcomingHomeworkResponse,
+  },
+};
+
+use chrono::{Local, NaiveDate};
+use http::Method;
+use serde::de::IgnoredAny;
+
+/// Fetch the full content of a day in the textbook:
+/// assignments due that day and the content of the sessions.
+pub async fn get_homework_day(
+  request_manager: &mut RequestManager,
+  student_id: u32,
+  date: NaiveDate,
+) -> Result<HomeworkDay, Error> {
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("/v3/eleves/{student_id}/cahierdetexte/{date}.awp?verbe=get"),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(EmptyRequest {})
+  .build()?;
+
+  request_manager.send_data(request).await
+}
+
+/// Fetch the homework due from today on, by due date.
+/// Only the subject and done state are given, not the content.
+pub async fn get_upcoming_homework(
+  request_manager: &mut RequestManager,
+  student_id: u32,
+) -> Result<UpcomingHomeworkResponse, Error> {
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("/v3/eleves/{student_id}/cahierdetexte.awp?verbe=get"),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(EmptyRequest {})
+  .build()?;
+
+  request_manager.send_data(request).await
+}
+
+/// Fetch the full content of every day between `start` and `end`
+/// (both included) that has homework due.
+///
+/// Days after today are picked from the upcoming homework, so only one
+/// request is sent per day with homework. Days up to today aren't part
+/// of it, each of them is fetched to find the ones with homework.
+pub async fn get_homework_range(
+  request_manager: &mut RequestManager,
+  student_id: u32,
+  start: NaiveDate,
+  end: NaiveDate,
+) -> Result<Vec<HomeworkDay>, Error> {
+  let today = Local::now().date_naive();
+  get_homework_range_from(request_manager, student_id, start, end, today).await
+}
+
+/// [`get_homework_range`] with `today` as seen by this device. Today is
+/// fetched on its own too since the school may already be on another day.
+async fn get_homework_range_from(
+  request_manager: &mut RequestManager,
+  student_id: u32,
+  start: NaiveDate,
+  end: NaiveDate,
+  today: NaiveDate,
+) -> Result<Vec<HomeworkDay>, Error> {
+  let mut days = Vec::new();
+
+  for date in start.iter_days().take_while(|date| *date <= end.min(today)) {
+    let day = get_homework_day(request_manager, student_id, date).await?;
+
+    if day.entries.iter().any(|entry| entry.assignment.is_some()) {
+      days.push(day);
+    }
+  }
+
+  if end <= today {
+    return Ok(days);
+  }
+
+  let upcoming = get_upcoming_homework(request_manager, student_id).await?;
+
+  for date in upcoming.days.into_keys() {
+    if date <= today || date < start || date > end {
+      continue;
+    }
+
+    days.push(get_homework_day(request_manager, student_id, date).await?);
+  }
+
+  Ok(days)
+}
+
+/// Mark an assignment as done or not done.
+pub async fn set_homework_done(
+  request_manager: &mut RequestManager,
+  student_id: u32,
+  homework_id: u32,
+  done: bool,
+) -> Result<(), Error> {
+  let form = if done {
+    HomeworkDoneRequest {
+      done: vec![homework_id],
+      not_done: vec![],
+    }
+  } else {
+    HomeworkDoneRequest {
+      done: vec![],
+      not_done: vec![homework_id],
+    }
+  };
+
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("/v3/eleves/{student_id}/cahierdetexte.awp?verbe=put"),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(form)
+  .build()?;
+
+  request_manager.send_data::<IgnoredAny>(request).await?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{get_homework_range_from, set_homework_done};
+  use crate::{api::RequestManager, mock};
+  use chrono::NaiveDate;
+  use serde_json::Value;
+
+  fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 1, day).unwrap()
+  }
+
+  async fn range(
+    request_manager: &mut RequestManager,
+    server: &mock::MockServer,
+    start: NaiveDate,
+    end: NaiveDate,
+    today: NaiveDate,
+  ) -> (Vec<NaiveDate>, Vec<String>) {
+    let sent = server.received().len();
+
+    let days =
+      get_homework_range_from(request_manager, 4179, start, end, today)
+        .await
+        .unwrap();
+
+    let paths = server
+      .received()
+      .into_iter()
+      .skip(sent)
+      .map(|request| request.path)
+      .collect();
+
+    (days.into_iter().map(|day| day.date).collect(), paths)
+  }
+
+  #[tokio::test]
+  async fn fetches_past_days_one_by_one() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let mut request_manager = login.request_manager();
+
+    let (days, paths) =
+      range(&mut request_manager, &server, date(13), date(15), date(31)).await;
+
+    assert_eq!(days, [date(14)]);
+    assert_eq!(
+      paths,
+      [
+        "/v3/eleves/4179/cahierdetexte/2025-01-13.awp",
+        "/v3/eleves/4179/cahierdetexte/2025-01-14.awp",
+        "/v3/eleves/4179/cahierdetexte/2025-01-15.awp",
+      ]
+    );
+  }
+
+  #[tokio::test]
+  async fn picks_future_days_from_upcoming_homework() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let mut request_manager = login.request_manager();
+
+    let (days, paths) =
+      range(&mut request_manager, &server, date(14), date(20), date(13)).await;
+
+    assert_eq!(days, [date(14), date(16)]);
+    assert_eq!(
+      paths,
+      [
+        "/v3/eleves/4179/cahierdetexte.awp",
+        "/v3/eleves/4179/cahierdetexte/2025-01-14.awp",
+        "/v3/eleves/4179/cahierdetexte/2025-01-16.awp",
+      ]
+    );
+  }
+
+  #[tokio::test]
+  async fn covers_ranges_across_today() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let mut request_manager = login.request_manager();
+
+    let (days, paths) =
+      range(&mut request_manager, &server, date(13), date(16), date(14)).await;
+
+    assert_eq!(days, [date(14), date(16)]);
+    assert_eq!(
+      paths,
+      [
+        "/v3/eleves/4179/cahierdetexte/2025-01-13.awp",
+        "/v3/eleves/4179/cahierdetexte/2025-01-14.awp",
+        "/v3/eleves/4179/cahierdetexte.awp",
+        "/v3/eleves/4179/cahierdetexte/2025-01-16.awp",
+      ]
+    );
+  }
+
+  #[tokio::test]
+  async fn can_set_homework_done() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let mut request_manager = login.request_manager();
+
+    set_homework_done(&mut request_manager, 4179, 5412, true)
+      .await
+      .unwrap();
+    set_homework_done(&mut request_manager, 4179, 5420, false)
+      .await
+      .unwrap();
+
+    let received = server.received();
+    let [done, not_done] = &received[received.len() - 2..] else {
+      unreachable!();
+    };
+
+    assert_eq!(done.path, "/v3/eleves/4179/cahierdetexte.awp");
+    assert_eq!(done.query["verbe"], "put");
+
+    let form = done.form::<Value>().unwrap();
+    assert_eq!(form["idDevoirsEffectues"], serde_json::json!([5412]));
+    assert_eq!(form["idDevoirsNonEffectues"], serde_json::json!([]));
+
+    let form = not_done.form::<Value>().unwrap();
+    assert_eq!(form["idDevoirsEffectues"], serde_json::json!([]));
+    assert_eq!(form["idDevoirsNonEffectues"], serde_json::json!([5420]));
+  }
+}
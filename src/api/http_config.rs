@@ -0,0 +1,176 @@
+use crate::api::Error;
+
+use reqwest::{Certificate, Client, Proxy};
+use std::time::Duration;
+
+/// Settings of the HTTP client shared by every request of a session.
+#[derive(Debug, Clone, Default)]
+pub struct HttpConfig {
+  client: Option<Client>,
+  api_url: Option<String>,
+  apip_url: Option<String>,
+  timeout: Option<Duration>,
+  connect_timeout: Option<Duration>,
+  https_proxy: Option<String>,
+  root_certificates: Vec<Certificate>,
+  user_agent: Option<String>,
+}
+
+impl HttpConfig {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Send requests through an existing client, so many sessions share
+  /// its connection pool. Timeouts, proxy and root certificates of this
+  /// config are then ignored, set them on the client instead.
+  pub fn client(mut self, client: Client) -> Self {
+    self.client = Some(client);
+    self
+  }
+
+  /// Send requests to another host than [`API_URL`](crate::api::API_URL),
+  /// such as a local mock of EcoleDirecte, see
+  /// [`RequestManager::api_url`](crate::api::RequestManager::api_url).
+  pub fn api_url(mut self, url: String) -> Self {
+    self.api_url = Some(url);
+    self
+  }
+
+  /// Send staff requests to another host than
+  /// [`APIP_URL`](crate::api::APIP_URL).
+  pub fn apip_url(mut self, url: String) -> Self {
+    self.apip_url = Some(url);
+    self
+  }
+
+  /// Timeout of a whole request, from connecting to reading the body.
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = Some(timeout);
+    self
+  }
+
+  pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+    self.connect_timeout = Some(timeout);
+    self
+  }
+
+  /// Proxy every HTTPS request through the given URL.
+  pub fn https_proxy(mut self, url: String) -> Self {
+    self.https_proxy = Some(url);
+    self
+  }
+
+  /// Trust an extra root certificate, such as the one of a proxy.
+  pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+    self.root_certificates.push(certificate);
+    self
+  }
+
+  /// Replace the default user agent, [`USER_AGENT`](crate::api::USER_AGENT).
+  pub fn user_agent(mut self, user_agent: String) -> Self {
+    self.user_agent = Some(user_agent);
+    self
+  }
+
+  pub(crate) fn user_agent_override(&self) -> Option<&str> {
+    self.user_agent.as_deref()
+  }
+
+  pub(crate) fn api_url_override(&self) -> Option<&str> {
+    self.api_url.as_deref()
+  }
+
+  pub(crate) fn apip_url_override(&self) -> Option<&str> {
+    self.apip_url.as_deref()
+  }
+
+  pub(crate) fn build_client(&self) -> Result<Client, Error> {
+    if let Some(client) = &self.client {
+      return Ok(client.clone());
+    }
+
+    let mut builder = Client::builder();
+
+    if let Some(timeout) = self.timeout {
+      builder = builder.timeout(timeout);
+    }
+
+    if let Some(timeout) = self.connect_timeout {
+      builder = builder.connect_timeout(timeout);
+    }
+
+    if let Some(url) = &self.https_proxy {
+      builder = builder.proxy(Proxy::https(url)?);
+    }
+
+    for certificate in &self.root_certificates {
+      builder = builder.add_root_certificate(certificate.clone());
+    }
+
+    Ok(builder.build()?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{api::LoginManager, mock};
+  use reqwest::{
+    Client,
+    header::{HeaderMap, HeaderValue},
+  };
+
+  #[tokio::test]
+  async fn sessions_can_share_a_client() {
+    let server = mock::MockServer::start().await.unwrap();
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-shared-client", HeaderValue::from_static("1"));
+    let client = Client::builder().default_headers(headers).build().unwrap();
+
+    let config = server.http_config().client(client);
+
+    for username in [mock::STUDENT_USERNAME, mock::FAMILY_USERNAME] {
+      let mut login = LoginManager::from_credentials(
+        username.into(),
+        mock::PASSWORD.into(),
+        None,
+      )
+      .with_http_config(&config)
+      .unwrap();
+
+      login.request().await.expect("login failed");
+    }
+
+    let received = server.received();
+    assert_eq!(received.len(), 4);
+    assert!(
+      received
+        .iter()
+        .all(|request| request.headers.contains_key("x-shared-client"))
+    );
+  }
+
+  #[tokio::test]
+  async fn sends_the_configured_user_agent() {
+    let server = mock::MockServer::start().await.unwrap();
+    let config = server.http_config().user_agent("pawdirecte-tests".into());
+
+    let mut login = LoginManager::from_credentials(
+      mock::STUDENT_USERNAME.into(),
+      mock::PASSWORD.into(),
+      None,
+    )
+    .with_http_config(&config)
+    .unwrap();
+
+    login.request().await.expect("login failed");
+
+    assert!(
+      server
+        .received()
+        .iter()
+        .all(|request| request.headers["user-agent"] == "pawdirecte-tests")
+    );
+  }
+}
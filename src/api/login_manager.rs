@@ -1,7 +1,11 @@
 use crate::{
-  api::{Authentication, Error, RequestBuilder, RequestManager},
+  api::{
+    Authentication, CredentialStore, Credentials, DoubleAuthAnswer,
+    DoubleAuthChallenge, Error, HttpConfig, RequestBuilder, RequestManager,
+    SESSION_STATE_VERSION, SessionState,
+  },
   definitions::{
-    models::Account,
+    models::{Account, AccountKind},
     requests::{DoubleAuthSolveRequest, EmptyRequest, LoginRequest},
     responses::{
       DoubleAuthChallengeResponse, DoubleAuthSolveResponse, LoginResponse,
@@ -12,10 +16,7 @@ use crate::{
 use cookie_parser::{CookiePair, parse_set_cookie};
 use http::Method;
 use reqwest::header::{self, SET_COOKIE};
-use std::{
-  sync::{Arc, Mutex},
-  vec,
-};
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug)]
 pub struct LoginManager {
@@ -48,7 +49,7 @@ impl LoginManager {
   pub fn from_access_token(
     username: String,
     access_token: String,
-    kind: String,
+    kind: AccountKind,
     device_uuid: String,
   ) -> Self {
     let authentication =
@@ -68,164 +69,237 @@ impl LoginManager {
     }
   }
 
-  /// Make a login request, this will define accounts and 2FA variable.
-  pub async fn request(&mut self) -> Result<(), Error> {
-    // 1. craft a request to grab GTK cookies for login.
-    let request =
-      RequestBuilder::<()>::new(Method::GET, "/v3/login.awp?gtk=1")?
-        .append_version()
-        .build()?;
-
-    // 2. send the request and get the response.
-    let (_, headers) = self.request_manager.send::<()>(request).await?;
-
-    // 3. extract new cookies from "set-cookie" header.
-    let cookies: Vec<CookiePair> = headers
-      .get_all(SET_COOKIE)
-      .iter()
-      .map(|cookie_header| {
-        parse_set_cookie(cookie_header.to_str().unwrap())
-          .unwrap()
-          .pair
-      })
-      .collect();
-
-    // 4. find the "GTK" cookie within all the new cookies.
-    let gtk = cookies
-      .iter()
-      .find(|pair| pair.name == "GTK")
-      .map(|pair| pair.value.clone());
-
-    // 5. make sure it exists.
-    if let Some(gtk) = gtk {
-      // 6. re-use the GWT (+extra) cookies for the authentication
-      let cookies = cookies
-        .iter()
-        .map(|pair| format!("{}={}", pair.name, pair.value))
-        .collect::<Vec<_>>()
-        .join("; ");
-
-      // 7. build the form data to authenticate.
-      // ---------------------------------------
-      // 7.1.1. we're not already authenticated, let's run the initial procedure.
-      let auth = self.authentication.lock().unwrap().clone();
-      let form = if auth.access_token.is_none() {
-        let double_auth = if self.double_auth.is_none() {
-          None
-        } else {
-          Some(vec![self.double_auth.clone().unwrap()])
-        };
-
-        LoginRequest {
-          device_uuid: auth.device_uuid,
-          is_reauth: false,
-          password: auth.password.trim().into(),
-          remember_me: Some(true),
-          username: auth.username,
-          access_token: None,
-          account_type: None,
-          double_auth,
-        }
-      }
-      // 7.2.1. we're already authenticated, re-use the access token.
-      else {
-        LoginRequest {
-          device_uuid: auth.device_uuid,
-          is_reauth: true,
-          password: auth.password.trim().into(),
-          remember_me: None,
-          access_token: auth.access_token,
-          double_auth: None,
-          username: auth.username,
-          account_type: auth.kind,
-        }
-      };
+  /// Log in with the credentials kept in `store` for `username`,
+  /// the store is then updated after every successful login.
+  pub fn from_store(
+    username: &str,
+    store: Arc<dyn CredentialStore>,
+  ) -> Result<Self, Error> {
+    let credentials = store
+      .load(username)?
+      .ok_or_else(|| Error::MissingCredentials(username.into()))?;
+
+    let double_auth = credentials.trusted_device.clone();
+    let authentication =
+      Arc::new(Mutex::new(Authentication::from(credentials)));
 
-      // 8. craft a request to login with GTK cookies, using the previous payload.
-      let mut request = RequestBuilder::new(Method::POST, "/v3/login.awp")?
-        .append_version()
-        .set_form(form)
-        .build()?;
+    let mut request_manager = RequestManager::new(authentication.clone());
+    request_manager.credential_store = Some(store);
 
-      // 9. append the GTK cookies to the crafted request.
-      let headers = request.headers_mut();
-      _ = headers.insert("X-GTK", gtk.parse()?);
-      _ = headers.insert(header::COOKIE, cookies.parse()?);
+    Ok(Self {
+      requires_2fa: false,
+      login_response: None,
+      request_manager,
+      authentication,
+      double_auth,
+    })
+  }
+
+  /// Save the credentials and the new access token to `store`
+  /// after every successful login.
+  pub fn with_credential_store(
+    mut self,
+    store: Arc<dyn CredentialStore>,
+  ) -> Self {
+    self.request_manager.credential_store = Some(store);
+    self
+  }
 
-      // 10. send the request and get the response.
-      let (json, _) =
-        self.request_manager.send::<LoginResponse>(request).await?;
+  /// Log in as a teacher or a staff member, they go through
+  /// [`APIP_URL`](crate::api::APIP_URL) instead of the usual host.
+  pub fn with_account_kind(self, kind: AccountKind) -> Self {
+    self.authentication.lock().unwrap().kind = Some(kind);
+    self
+  }
 
-      // 10. we're sure that JSON is given to user.
-      let json = json.unwrap();
+  /// Send the `cn`/`cv` identifiers of a device that already solved
+  /// the double auth, so a password login skips the challenge.
+  pub fn with_trusted_device(mut self, cn: String, cv: String) -> Self {
+    self.double_auth = Some(DoubleAuthSolveResponse::new(cn, cv));
+    self
+  }
 
-      match json.code {
-        505 => Err(Error::BadCredentials()),
-        517 => Err(Error::InvalidVersion()),
-        535 => Err(Error::EstablishmentUnavailable()),
-        _ => {
-          // 11. check if 2fa is required.
-          self.requires_2fa = json.code == 250;
+  /// Identifiers of this device once the double auth has been solved,
+  /// keep them to give to [`LoginManager::with_trusted_device`] later.
+  pub fn trusted_device(&self) -> Option<&DoubleAuthSolveResponse> {
+    self.double_auth.as_ref()
+  }
 
-          // 12. assign the login response, for later usage.
-          self.login_response = Some(json.data);
+  /// Restore a login saved with [`LoginManager::export_state`],
+  /// its accounts are available right away.
+  ///
+  /// The HTTP config, base URLs and credential store aren't part of the
+  /// state, give them again with [`LoginManager::with_http_config`],
+  /// [`LoginManager::with_base_urls`] and
+  /// [`LoginManager::with_credential_store`].
+  pub fn from_state(state: SessionState) -> Result<Self, Error> {
+    if state.version != SESSION_STATE_VERSION {
+      return Err(Error::UnsupportedSessionState(state.version));
+    }
 
-          Ok(())
-        }
+    let authentication = Arc::new(Mutex::new(state.authentication));
+
+    Ok(Self {
+      requires_2fa: false,
+      login_response: state.login_response,
+      request_manager: RequestManager::new(authentication.clone()),
+      authentication,
+      double_auth: state.double_auth,
+    })
+  }
+
+  /// Save the current login, to restore it later with
+  /// [`LoginManager::from_state`]. The password is never part of it.
+  pub fn export_state(&self) -> SessionState {
+    SessionState {
+      version: SESSION_STATE_VERSION,
+      authentication: self.authentication.lock().unwrap().clone(),
+      login_response: self.login_response.clone(),
+      double_auth: self.double_auth.clone(),
+    }
+  }
+
+  /// Use a custom HTTP client for every request made from this login,
+  /// including the ones of a [`Session`](crate::api::Session) built from it.
+  pub fn with_http_config(
+    mut self,
+    config: &HttpConfig,
+  ) -> Result<Self, Error> {
+    let credential_store = self.request_manager.credential_store.take();
+
+    self.request_manager =
+      RequestManager::with_config(self.authentication.clone(), config)?;
+    self.request_manager.credential_store = credential_store;
+
+    Ok(self)
+  }
+
+  /// Send every request of this login to other hosts than
+  /// [`API_URL`](crate::api::API_URL) and [`APIP_URL`](crate::api::APIP_URL),
+  /// such as a mock of EcoleDirecte. Call it after
+  /// [`LoginManager::with_http_config`], which sets the hosts of its config.
+  pub fn with_base_urls(
+    mut self,
+    api_url: &str,
+    apip_url: &str,
+  ) -> Result<Self, Error> {
+    self.request_manager.set_base_urls(api_url, apip_url)?;
+    Ok(self)
+  }
+
+  /// Make a login request, this will define accounts and 2FA variable.
+  ///
+  /// When the credentials can't be saved to the store, the login is done
+  /// all the same and [`Error::CredentialStoreFailed`] is returned.
+  pub async fn request(&mut self) -> Result<(), Error> {
+    // 1. build the form data to authenticate.
+    // ---------------------------------------
+    // 1.1. the password is known, let's run the initial procedure.
+    let auth = self.authentication.lock().unwrap().clone();
+    let form = if auth.has_password() || auth.access_token.is_none() {
+      let double_auth = self
+        .double_auth
+        .clone()
+        .map(|double_auth| vec![double_auth]);
+
+      LoginRequest {
+        device_uuid: auth.device_uuid,
+        is_reauth: false,
+        password: auth.password.trim().into(),
+        remember_me: Some(true),
+        username: auth.username,
+        access_token: None,
+        account_type: auth.kind,
+        double_auth,
       }
-    } else {
-      Err(Error::CookieGtkNotFound())
     }
+    // 1.2. only an access token is known, re-use it.
+    else {
+      reauth_form(auth)
+    };
+
+    // 2. send the form along with fresh GTK cookies.
+    let (code, response) = login(&self.request_manager, form).await?;
+
+    // 3. check if 2fa is required.
+    self.requires_2fa = code == 250;
+
+    // 4. keep the access token to re-authenticate once the token expires.
+    if !self.requires_2fa {
+      remember_access_token(&self.request_manager, &response);
+    }
+
+    // 5. assign the login response, for later usage.
+    self.login_response = Some(response);
+
+    // 6. save the credentials, the login is kept even if it fails.
+    if !self.requires_2fa {
+      save_credentials(&self.request_manager, self.double_auth.as_ref())?;
+    }
+
+    Ok(())
   }
 
   pub async fn get_2fa_challenge(
     &mut self,
-  ) -> Result<DoubleAuthChallengeResponse, Error> {
-    let auth = self.authentication.lock().unwrap().clone();
-
+  ) -> Result<DoubleAuthChallenge, Error> {
     let request = RequestBuilder::new(
+      self.request_manager.api_url(),
       Method::POST,
       "/v3/connexion/doubleauth.awp?verbe=get",
     )?
     .append_version()
-    .set_token(auth.token.unwrap())?
+    .set_token(self.request_manager.token()?)?
     .set_form(EmptyRequest {})
     .build()?;
 
-    let (json, _) = self
-      .request_manager
-      .send::<DoubleAuthChallengeResponse>(request)
-      .await?;
+    let challenge: DoubleAuthChallengeResponse =
+      self.request_manager.send_data(request).await?;
 
-    Ok(json.unwrap().data)
+    Ok(challenge.into())
   }
 
+  /// Send the answer picked from the challenge, a wrong one is reported
+  /// as [`Error::DoubleAuthRejected`]. Call [`LoginManager::request`]
+  /// once it has been accepted.
   pub async fn solve_2fa_challenge(
     &mut self,
-    answer: String,
+    answer: DoubleAuthAnswer,
   ) -> Result<(), Error> {
-    let auth = self.authentication.lock().unwrap().clone();
-
     let request = RequestBuilder::new(
+      self.request_manager.api_url(),
       Method::POST,
       "/v3/connexion/doubleauth.awp?verbe=post",
     )?
     .append_version()
-    .set_token(auth.token.unwrap())?
-    .set_form(DoubleAuthSolveRequest { answer })
+    .set_token(self.request_manager.token()?)?
+    .set_form(DoubleAuthSolveRequest {
+      answer: answer.into(),
+    })
     .build()?;
 
-    let (json, _) = self
-      .request_manager
-      .send::<DoubleAuthSolveResponse>(request)
-      .await?;
+    let double_auth: DoubleAuthSolveResponse =
+      match self.request_manager.send_data(request).await {
+        Err(Error::Api { code: 202, message }) => {
+          return Err(Error::DoubleAuthRejected(message));
+        }
+        result => result?,
+      };
 
-    let json = json.unwrap().data;
-    self.double_auth = Some(json);
+    if double_auth.is_empty() {
+      return Err(Error::DoubleAuthRejected(String::new()));
+    }
 
+    self.double_auth = Some(double_auth);
     Ok(())
   }
 
+  /// Request manager sharing this login's authentication,
+  /// use it to call the data endpoints once logged in.
+  pub fn request_manager(&self) -> RequestManager {
+    self.request_manager.clone()
+  }
+
   pub fn accounts(&self) -> Result<Vec<Account>, Error> {
     if let Some(login_response) = &self.login_response {
       Ok(login_response.accounts.clone())
@@ -234,3 +308,521 @@ impl LoginManager {
     }
   }
 }
+
+/// Login form re-using the access token of a previous login.
+fn reauth_form(auth: Authentication) -> LoginRequest {
+  LoginRequest {
+    device_uuid: auth.device_uuid,
+    is_reauth: true,
+    password: "???".into(),
+    remember_me: None,
+    access_token: auth.access_token,
+    double_auth: None,
+    username: auth.username,
+    account_type: auth.kind,
+  }
+}
+
+/// Keep the access token of the logged in account,
+/// to log in again once the token expires.
+fn remember_access_token(
+  request_manager: &RequestManager,
+  response: &LoginResponse,
+) {
+  let mut auth = request_manager.authentication.lock().unwrap();
+
+  // the access token is given to the account that logged in,
+  // others are the ones it's linked to.
+  let accounts = &response.accounts;
+  let account = accounts
+    .iter()
+    .find(|account| account.username == auth.username)
+    .or_else(|| accounts.iter().find(|account| account.is_main))
+    .or_else(|| accounts.first());
+
+  if let Some(account) = account {
+    auth.access_token = Some(account.access_token.clone());
+    auth.kind = Some(account.kind.clone());
+  }
+}
+
+/// Save the credentials when there's a store, along with the trusted
+/// device or the one that was already saved.
+fn save_credentials(
+  request_manager: &RequestManager,
+  trusted_device: Option<&DoubleAuthSolveResponse>,
+) -> Result<(), Error> {
+  let Some(store) = &request_manager.credential_store else {
+    return Ok(());
+  };
+
+  let mut credentials =
+    Credentials::from(&*request_manager.authentication.lock().unwrap());
+
+  let failed = |error: Error| Error::CredentialStoreFailed(Box::new(error));
+
+  credentials.trusted_device = match trusted_device {
+    Some(device) => Some(device.clone()),
+    None => store
+      .load(&credentials.username)
+      .map_err(failed)?
+      .and_then(|saved| saved.trusted_device),
+  };
+
+  store.save(&credentials).map_err(failed)
+}
+
+/// Grab the GTK cookies then send the login form with them,
+/// returns the response code along with the login response.
+async fn login(
+  request_manager: &RequestManager,
+  form: LoginRequest,
+) -> Result<(u32, LoginResponse), Error> {
+  // teachers and staff have their own host.
+  let on_apip = form
+    .account_type
+    .as_ref()
+    .is_some_and(AccountKind::uses_apip);
+
+  // 1. craft a request to grab GTK cookies for login.
+  let request = login_request::<()>(
+    request_manager,
+    on_apip,
+    Method::GET,
+    "/v3/login.awp?gtk=1",
+  )?
+  .append_version()
+  .build()?;
+
+  // 2. send the request and get the response.
+  let (_, headers) = request_manager.execute(request).await?;
+
+  // 3. extract new cookies from "set-cookie" header.
+  let cookies = headers
+    .get_all(SET_COOKIE)
+    .iter()
+    .map(|cookie_header| Ok(parse_set_cookie(cookie_header.to_str()?)?.pair))
+    .collect::<Result<Vec<CookiePair>, Error>>()?;
+
+  // 4. find the "GTK" cookie within all the new cookies.
+  let gtk = cookies
+    .iter()
+    .find(|pair| pair.name == "GTK")
+    .map(|pair| pair.value.clone());
+
+  // 5. make sure it exists.
+  let Some(gtk) = gtk else {
+    return Err(Error::CookieGtkNotFound());
+  };
+
+  // 6. re-use the GWT (+extra) cookies for the authentication
+  let cookies = cookies
+    .iter()
+    .map(|pair| format!("{}={}", pair.name, pair.value))
+    .collect::<Vec<_>>()
+    .join("; ");
+
+  // 7. craft a request to login with GTK cookies, using the given payload.
+  let mut request =
+    login_request(request_manager, on_apip, Method::POST, "/v3/login.awp")?
+      .append_version()
+      .set_form(form)
+      .build()?;
+
+  // 8. append the GTK cookies to the crafted request.
+  let headers = request.headers_mut();
+  _ = headers.insert("X-GTK", gtk.parse()?);
+  _ = headers.insert(header::COOKIE, cookies.parse()?);
+
+  // 9. send the request and read the response.
+  let (body, _) = request_manager.execute(request).await?;
+  let json = request_manager.read_response::<LoginResponse>(&body)?;
+
+  Ok((json.code, json.data))
+}
+
+fn login_request<T: serde::Serialize>(
+  request_manager: &RequestManager,
+  on_apip: bool,
+  method: Method,
+  path: &str,
+) -> Result<RequestBuilder<T>, Error> {
+  let base_url = if on_apip {
+    request_manager.apip_url()
+  } else {
+    request_manager.api_url()
+  };
+
+  RequestBuilder::new(base_url, method, path)
+}
+
+/// Log in again using the access token of the previous login,
+/// used by [`RequestManager`] when the token has expired.
+pub(crate) async fn reauthenticate(
+  request_manager: &RequestManager,
+) -> Result<(), Error> {
+  let auth = request_manager.authentication.lock().unwrap().clone();
+
+  if auth.access_token.is_none() {
+    return Err(Error::WrongLoginState());
+  }
+
+  let (code, response) = login(request_manager, reauth_form(auth)).await?;
+
+  if code != 200 {
+    return Err(Error::Api {
+      code,
+      message: "re-authentication has been refused".into(),
+    });
+  }
+
+  remember_access_token(request_manager, &response);
+  save_credentials(request_manager, None)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{LoginManager, reauth_form};
+  use crate::{
+    api::{
+      Authentication, CredentialStore, Credentials, Error,
+      MemoryCredentialStore, get_school_life,
+    },
+    definitions::models::AccountKind,
+    mock,
+  };
+  use serde_json::Value;
+  use std::sync::Arc;
+
+  fn login_manager(
+    server: &mock::MockServer,
+    username: &str,
+    password: &str,
+  ) -> LoginManager {
+    LoginManager::from_credentials(username.into(), password.into(), None)
+      .with_http_config(&server.http_config())
+      .expect("invalid mock config")
+  }
+
+  #[tokio::test]
+  async fn can_login_with_credentials() {
+    let server = mock::MockServer::start().await.unwrap();
+
+    for username in [
+      mock::STUDENT_USERNAME,
+      mock::PRIMARY_STUDENT_USERNAME,
+      mock::FAMILY_USERNAME,
+    ] {
+      let mut login = login_manager(&server, username, mock::PASSWORD);
+      login.request().await.expect("login failed");
+
+      assert!(!login.requires_2fa);
+      assert_eq!(login.accounts().unwrap()[0].username, username);
+    }
+  }
+
+  #[tokio::test]
+  async fn can_login_with_access_token() {
+    let server = mock::MockServer::start().await.unwrap();
+
+    let mut login = LoginManager::from_access_token(
+      mock::FAMILY_USERNAME.into(),
+      mock::access_token(mock::FAMILY_USERNAME),
+      AccountKind::Family,
+      "device".into(),
+    )
+    .with_http_config(&server.http_config())
+    .unwrap();
+
+    login.request().await.expect("login failed");
+    assert!(login.accounts().is_ok());
+  }
+
+  #[tokio::test]
+  async fn logs_in_again_with_the_password() {
+    let server = mock::MockServer::start().await.unwrap();
+    let mut login =
+      login_manager(&server, mock::FAMILY_USERNAME, mock::PASSWORD);
+
+    login.request().await.expect("login failed");
+    login.request().await.expect("login failed");
+
+    let logins = server
+      .received()
+      .into_iter()
+      .filter(|request| request.method == "POST")
+      .filter_map(|request| request.form::<Value>())
+      .collect::<Vec<_>>();
+
+    assert_eq!(logins.len(), 2);
+    assert!(logins.iter().all(|form| form["isReLogin"] == false));
+  }
+
+  #[tokio::test]
+  async fn retries_once_when_the_token_has_expired() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let mut request_manager = login.request_manager();
+
+    server.expire_tokens();
+    let sent = server.received().len();
+
+    get_school_life(&mut request_manager, 4179)
+      .await
+      .expect("request failed after re-authentication");
+
+    let received = server.received().split_off(sent);
+    let paths = received
+      .iter()
+      .map(|request| (request.method.as_str(), request.path.as_str()))
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      paths,
+      [
+        ("POST", "/v3/eleves/4179/viescolaire.awp"),
+        ("GET", "/v3/login.awp"),
+        ("POST", "/v3/login.awp"),
+        ("POST", "/v3/eleves/4179/viescolaire.awp"),
+      ]
+    );
+
+    let form: Value = received[2].form().unwrap();
+    assert_eq!(form["isReLogin"], true);
+    assert_eq!(
+      form["accesstoken"],
+      mock::access_token(mock::STUDENT_USERNAME)
+    );
+  }
+
+  #[tokio::test]
+  async fn can_login_on_custom_base_urls() {
+    let server = mock::MockServer::start().await.unwrap();
+
+    let mut login = LoginManager::from_credentials(
+      mock::STUDENT_USERNAME.into(),
+      mock::PASSWORD.into(),
+      None,
+    )
+    .with_base_urls(&format!("{}/", server.url()), &server.url())
+    .unwrap();
+
+    login.request().await.expect("login failed");
+
+    let request_manager = login.request_manager();
+    assert_eq!(request_manager.api_url(), server.url());
+    assert_eq!(server.received().len(), 2);
+  }
+
+  #[tokio::test]
+  async fn can_solve_double_auth() {
+    let server = mock::MockServer::start().await.unwrap();
+    let mut login =
+      login_manager(&server, mock::DOUBLE_AUTH_USERNAME, mock::PASSWORD);
+
+    login.request().await.expect("login failed");
+    assert!(login.requires_2fa);
+
+    let challenge = login.get_2fa_challenge().await.unwrap();
+    assert_eq!(challenge.question(), mock::DOUBLE_AUTH_QUESTION);
+
+    let answer = challenge.choose(mock::DOUBLE_AUTH_ANSWER).unwrap();
+    login.solve_2fa_challenge(answer).await.unwrap();
+
+    login.request().await.expect("login failed");
+    assert!(!login.requires_2fa);
+    assert!(login.accounts().is_ok());
+  }
+
+  #[tokio::test]
+  async fn can_skip_double_auth_on_trusted_device() {
+    let server = mock::MockServer::start().await.unwrap();
+    let mut login =
+      login_manager(&server, mock::DOUBLE_AUTH_USERNAME, mock::PASSWORD);
+
+    login.request().await.expect("login failed");
+    let challenge = login.get_2fa_challenge().await.unwrap();
+    let answer = challenge.choose(mock::DOUBLE_AUTH_ANSWER).unwrap();
+    login.solve_2fa_challenge(answer).await.unwrap();
+
+    let device = login.trusted_device().unwrap().clone();
+
+    let mut login =
+      login_manager(&server, mock::DOUBLE_AUTH_USERNAME, mock::PASSWORD)
+        .with_trusted_device(device.name().into(), device.value().into());
+
+    login.request().await.expect("login failed");
+    assert!(!login.requires_2fa);
+    assert!(login.accounts().is_ok());
+  }
+
+  #[tokio::test]
+  async fn reports_rejected_double_auth_answer() {
+    let server = mock::MockServer::start().await.unwrap();
+    let mut login =
+      login_manager(&server, mock::DOUBLE_AUTH_USERNAME, mock::PASSWORD);
+
+    login.request().await.expect("login failed");
+
+    let challenge = login.get_2fa_challenge().await.unwrap();
+    let wrong = mock::DOUBLE_AUTH_ANSWERS
+      .into_iter()
+      .find(|answer| *answer != mock::DOUBLE_AUTH_ANSWER)
+      .unwrap();
+
+    let result = login
+      .solve_2fa_challenge(challenge.choose(wrong).unwrap())
+      .await;
+    assert!(matches!(result, Err(Error::DoubleAuthRejected(_))));
+  }
+
+  #[tokio::test]
+  async fn can_restore_exported_state() {
+    let server = mock::MockServer::start().await.unwrap();
+    let mut login =
+      login_manager(&server, mock::FAMILY_USERNAME, mock::PASSWORD);
+    login.request().await.expect("login failed");
+
+    let json = serde_json::to_string(&login.export_state()).unwrap();
+    assert!(!json.contains(mock::PASSWORD));
+
+    let state = serde_json::from_str(&json).unwrap();
+    let restored = LoginManager::from_state(state).unwrap();
+    let auth = restored.authentication.lock().unwrap().clone();
+
+    assert_eq!(auth.token, login.request_manager.token().ok());
+    assert_eq!(
+      auth.access_token,
+      Some(mock::access_token(mock::FAMILY_USERNAME))
+    );
+    assert_eq!(
+      restored.accounts().unwrap()[0].username,
+      mock::FAMILY_USERNAME
+    );
+  }
+
+  #[tokio::test]
+  async fn restored_state_can_be_configured_again() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let state = login.export_state();
+
+    let store = Arc::new(MemoryCredentialStore::new());
+    let mut restored = LoginManager::from_state(state)
+      .unwrap()
+      .with_http_config(&server.http_config())
+      .unwrap()
+      .with_credential_store(store.clone());
+
+    let request_manager = restored.request_manager();
+    assert_eq!(request_manager.api_url(), server.url());
+    assert_eq!(
+      request_manager.token().ok(),
+      login.request_manager().token().ok()
+    );
+
+    // the access token of the state is enough to log in again.
+    restored.request().await.expect("login failed");
+    assert!(store.load(mock::STUDENT_USERNAME).unwrap().is_some());
+  }
+
+  #[test]
+  fn rejects_unknown_state_version() {
+    let login =
+      LoginManager::from_credentials("user".into(), "pass".into(), None);
+
+    let mut state = login.export_state();
+    state.version += 1;
+
+    let result = LoginManager::from_state(state);
+    assert!(matches!(result, Err(Error::UnsupportedSessionState(_))));
+  }
+
+  #[tokio::test]
+  async fn can_login_from_credential_store() {
+    let server = mock::MockServer::start().await.unwrap();
+    let store = Arc::new(MemoryCredentialStore::new());
+
+    let mut login =
+      login_manager(&server, mock::STUDENT_USERNAME, mock::PASSWORD)
+        .with_credential_store(store.clone());
+    login.request().await.expect("login failed");
+
+    let credentials = store.load(mock::STUDENT_USERNAME).unwrap().unwrap();
+    assert_eq!(
+      credentials.access_token,
+      Some(mock::access_token(mock::STUDENT_USERNAME))
+    );
+
+    let mut login = LoginManager::from_store(mock::STUDENT_USERNAME, store)
+      .unwrap()
+      .with_http_config(&server.http_config())
+      .unwrap();
+    login.request().await.expect("login failed");
+
+    let output = format!("{login:?}");
+    assert!(!output.contains(mock::PASSWORD));
+    assert!(!output.contains(&mock::access_token(mock::STUDENT_USERNAME)));
+  }
+
+  /// Store that can never be written to.
+  #[derive(Debug)]
+  struct ReadOnlyStore;
+
+  impl CredentialStore for ReadOnlyStore {
+    fn load(&self, _: &str) -> Result<Option<Credentials>, Error> {
+      Ok(None)
+    }
+
+    fn save(&self, _: &Credentials) -> Result<(), Error> {
+      Err(std::io::Error::from(std::io::ErrorKind::ReadOnlyFilesystem).into())
+    }
+
+    fn remove(&self, _: &str) -> Result<(), Error> {
+      Ok(())
+    }
+  }
+
+  #[tokio::test]
+  async fn keeps_the_login_when_credentials_cant_be_saved() {
+    let server = mock::MockServer::start().await.unwrap();
+    let mut login =
+      login_manager(&server, mock::STUDENT_USERNAME, mock::PASSWORD)
+        .with_credential_store(Arc::new(ReadOnlyStore));
+
+    let result = login.request().await;
+    assert!(matches!(result, Err(Error::CredentialStoreFailed(_))));
+    assert!(login.accounts().is_ok());
+    assert!(login.request_manager().token().is_ok());
+  }
+
+  #[test]
+  fn login_form_hides_secrets() {
+    let mut auth =
+      Authentication::from_credentials("user".into(), "secret".into(), None);
+    auth.access_token = Some("secret-access-token".into());
+
+    let output = format!("{:?}", reauth_form(auth));
+    assert!(output.contains("user"));
+    assert!(!output.contains("secret"));
+  }
+
+  #[tokio::test]
+  async fn reports_login_errors() {
+    let server = mock::MockServer::start().await.unwrap();
+
+    let mut login = login_manager(&server, mock::STUDENT_USERNAME, "wrong");
+    let result = login.request().await;
+    assert!(matches!(result, Err(Error::BadCredentials(_))));
+
+    let mut login =
+      login_manager(&server, mock::OUTDATED_USERNAME, mock::PASSWORD);
+    let result = login.request().await;
+    assert!(matches!(result, Err(Error::InvalidVersion(_))));
+
+    let mut login =
+      login_manager(&server, mock::CLOSED_USERNAME, mock::PASSWORD);
+    let result = login.request().await;
+    assert!(matches!(result, Err(Error::EstablishmentUnavailable(_))));
+  }
+}
@@ -0,0 +1,323 @@
+use crate::{
+  api::{Error, RequestBuilder, RequestManager},
+  definitions::{
+    models::{
+      Account, Contact, ContactKind, Message, MessageBox, MessageFolder,
+    },
+    requests::{
+      ContactSearchRequest, MessagesActionRequest, MessagesRequest,
+      OutgoingMessage, Recipient, RecipientGroup, SendMessageRequest,
+    },
+    responses::{ContactsResponse, MessagesResponse},
+  },
+};
+
+use http::Method;
+use serde::de::IgnoredAny;
+
+/// Messages are owned by the logged in account,
+/// parents read their family mailbox and not their children's.
+fn messages_path(account: &Account) -> Result<String, Error> {
+  let owner = account
+    .kind
+    .path_prefix()
+    .ok_or_else(|| Error::UnsupportedAccountKind(account.kind.code().into()))?;
+
+  Ok(format!("/v3/{owner}/{}/messages", account.id))
+}
+
+/// List a page of messages from a mailbox, `page` starts at `0`.
+pub async fn get_messages(
+  request_manager: &mut RequestManager,
+  account: &Account,
+  mailbox: MessageBox,
+  page: u32,
+  items_per_page: u32,
+) -> Result<MessagesResponse, Error> {
+  get_messages_in_folder(
+    request_manager,
+    account,
+    mailbox,
+    0,
+    page,
+    items_per_page,
+  )
+  .await
+}
+
+/// List a page of messages from a custom folder of the received mailbox.
+pub async fn get_messages_in_folder(
+  request_manager: &mut RequestManager,
+  account: &Account,
+  mailbox: MessageBox,
+  folder_id: u32,
+  page: u32,
+  items_per_page: u32,
+) -> Result<MessagesResponse, Error> {
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("{}.awp?verbe=get", messages_path(account)?),
+  )?
+  .append_version()
+  .append_query("typeRecuperation", mailbox.as_str())
+  .append_query("idClasseur", &folder_id.to_string())
+  .append_query("orderBy", "date")
+  .append_query("order", "desc")
+  .append_query("page", &page.to_string())
+  .append_query("itemsPerPage", &items_per_page.to_string())
+  .append_query("getAll", "0")
+  .set_token(request_manager.token()?)?
+  .set_form(MessagesRequest {
+    school_year: String::new(),
+  })
+  .build()?;
+
+  request_manager.send_data(request).await
+}
+
+/// List the custom folders of the account.
+pub async fn get_message_folders(
+  request_manager: &mut RequestManager,
+  account: &Account,
+) -> Result<Vec<MessageFolder>, Error> {
+  // folders are only given alongside a listing, keep it as small as possible.
+  let response =
+    get_messages(request_manager, account, MessageBox::Received, 0, 1).await?;
+
+  Ok(response.folders)
+}
+
+/// Read a single message, its `content` is decoded from base64.
+///
+/// Reading a received message marks it as read.
+pub async fn get_message(
+  request_manager: &mut RequestManager,
+  account: &Account,
+  mailbox: MessageBox,
+  message_id: u32,
+) -> Result<Message, Error> {
+  let mode = match mailbox {
+    MessageBox::Sent | MessageBox::Draft => "expediteur",
+    MessageBox::Received | MessageBox::Archived => "destinataire",
+  };
+
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("{}/{message_id}.awp", messages_path(account)?),
+  )?
+  .append_query("verbe", "get")
+  .append_query("mode", mode)
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(MessagesRequest {
+    school_year: String::new(),
+  })
+  .build()?;
+
+  request_manager.send_data(request).await
+}
+
+async fn send_messages_action(
+  request_manager: &mut RequestManager,
+  account: &Account,
+  form: MessagesActionRequest,
+) -> Result<(), Error> {
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("{}.awp?verbe=put", messages_path(account)?),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(form)
+  .build()?;
+
+  request_manager.send_data::<IgnoredAny>(request).await?;
+  Ok(())
+}
+
+/// Mark messages as read or unread.
+pub async fn set_messages_read(
+  request_manager: &mut RequestManager,
+  account: &Account,
+  message_ids: Vec<u32>,
+  read: bool,
+) -> Result<(), Error> {
+  let action = if read {
+    "marquerCommeLu"
+  } else {
+    "marquerCommeNonLu"
+  };
+
+  send_messages_action(
+    request_manager,
+    account,
+    MessagesActionRequest {
+      action: action.into(),
+      ids: message_ids,
+      folder_id: None,
+    },
+  )
+  .await
+}
+
+/// Move messages to a custom folder, `0` moves them back to the inbox.
+pub async fn move_messages(
+  request_manager: &mut RequestManager,
+  account: &Account,
+  message_ids: Vec<u32>,
+  folder_id: u32,
+) -> Result<(), Error> {
+  send_messages_action(
+    request_manager,
+    account,
+    MessagesActionRequest {
+      action: "deplacer".into(),
+      ids: message_ids,
+      folder_id: Some(folder_id),
+    },
+  )
+  .await
+}
+
+/// Search the people the account is allowed to write to.
+pub async fn search_contacts(
+  request_manager: &mut RequestManager,
+  kind: ContactKind,
+  query: String,
+) -> Result<Vec<Contact>, Error> {
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("/v3/messagerie/contacts/{}.awp?verbe=get", kind.as_str()),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(ContactSearchRequest { query })
+  .build()?;
+
+  let response: ContactsResponse = request_manager.send_data(request).await?;
+  Ok(response.contacts)
+}
+
+/// Send a new message, `content` is the HTML body.
+pub async fn send_message(
+  request_manager: &mut RequestManager,
+  account: &Account,
+  recipients: Vec<Contact>,
+  subject: String,
+  content: String,
+) -> Result<(), Error> {
+  let recipients = recipients
+    .into_iter()
+    .map(|contact| Recipient {
+      contact,
+      field: "to".into(),
+    })
+    .collect();
+
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("{}.awp?verbe=post", messages_path(account)?),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(SendMessageRequest {
+    message: OutgoingMessage {
+      recipient_groups: vec![RecipientGroup { recipients }],
+      subject,
+      content,
+      transferred_files: vec![],
+      files: vec![],
+      is_draft: false,
+    },
+    school_year: String::new(),
+  })
+  .build()?;
+
+  request_manager.send_data::<IgnoredAny>(request).await?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{get_message, get_messages, send_message};
+  use crate::{
+    definitions::models::{Contact, MessageBox},
+    mock,
+  };
+  use serde_json::Value;
+
+  #[tokio::test]
+  async fn can_list_and_read_messages() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let account = &login.accounts().unwrap()[0];
+    let mut request_manager = login.request_manager();
+
+    let response =
+      get_messages(&mut request_manager, account, MessageBox::Received, 0, 20)
+        .await
+        .unwrap();
+
+    assert_eq!(response.folders[0].name, "Sorties");
+    assert_eq!(response.messages.received.len(), 2);
+    assert_eq!(response.pagination.received_unread_count, 1);
+
+    let message =
+      get_message(&mut request_manager, account, MessageBox::Sent, 4242)
+        .await
+        .unwrap();
+
+    assert!(message.content.contains("La sortie au musée"));
+    assert_eq!(message.files[0].name, "autorisation.pdf");
+
+    let received = server.received();
+    let request = received.last().unwrap();
+    assert_eq!(request.path, "/v3/eleves/4179/messages/4242.awp");
+    assert_eq!(request.query["mode"], "expediteur");
+  }
+
+  #[tokio::test]
+  async fn can_send_message() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let account = &login.accounts().unwrap()[0];
+    let mut request_manager = login.request_manager();
+
+    let recipient: Contact = serde_json::from_value(serde_json::json!({
+      "id": 12,
+      "civilite": "M.",
+      "prenom": "Paul",
+      "nom": "DUPONT",
+      "role": "P",
+    }))
+    .unwrap();
+
+    send_message(
+      &mut request_manager,
+      account,
+      vec![recipient],
+      "Absence".into(),
+      "<p>Bonjour</p>".into(),
+    )
+    .await
+    .unwrap();
+
+    let received = server.received();
+    let request = received.last().unwrap();
+    assert_eq!(request.query["verbe"], "post");
+
+    let form: Value = request.form().unwrap();
+    let message = &form["message"];
+    assert_eq!(message["subject"], "Absence");
+    assert_eq!(message["content"], "PHA+Qm9uam91cjwvcD4=");
+    assert_eq!(
+      message["groupesDestinataires"][0]["destinataires"][0]["id"],
+      12
+    );
+  }
+}
@@ -1,7 +1,23 @@
 automod::dir!("src/api");
 
 pub use authentication::*;
+pub use canteen::*;
+pub use cloud::*;
 pub use constants::*;
+pub use credential_store::*;
+pub use documents::*;
+pub use double_auth::*;
+pub use download::*;
 pub use errors::*;
+pub use finances::*;
+pub use grades::*;
+pub use homework::*;
+pub use http_config::*;
 pub use login_manager::*;
+pub use messages::*;
 pub use request_manager::*;
+pub use school_life::*;
+pub use session::*;
+pub use session_state::*;
+pub use teachers::*;
+pub use timetable::*;
@@ -1,61 +1,305 @@
 use std::collections::HashMap;
-use std::fmt::Debug;
+use std::io::Read;
 use std::sync::{Arc, Mutex};
 
 use reqwest::{
-  Client, Request,
-  header::{self, HeaderMap},
+  Client, Request, Response,
+  blocking::multipart::{Form, Part},
+  header::{self, HeaderMap, HeaderValue},
 };
+use serde::de::{DeserializeOwned, IgnoredAny};
 use url::Url;
 
 use crate::{
-  api::{API_URL, API_VERSION, Authentication, Error, USER_AGENT},
+  api::{
+    API_URL, API_VERSION, APIP_URL, Authentication, CredentialStore, Download,
+    Error, HttpConfig, USER_AGENT, login_manager::reauthenticate,
+  },
   definitions::api::APIResponseWrap,
 };
 
+/// Response codes sent by EcoleDirecte when the token has expired.
+const TOKEN_EXPIRED_CODES: [u32; 2] = [520, 525];
+
+/// Sends requests for a session, all of them share the same HTTP client
+/// so connections are pooled.
 #[derive(Debug, Clone)]
 pub struct RequestManager {
-  authentication: Arc<Mutex<Authentication>>,
+  pub(crate) authentication: Arc<Mutex<Authentication>>,
+  client: Client,
+  user_agent: Option<HeaderValue>,
+  api_url: String,
+  apip_url: String,
+  /// Updated with the new access token after every login.
+  pub(crate) credential_store: Option<Arc<dyn CredentialStore>>,
+}
+
+/// Validate a base URL, without its trailing slash
+/// since paths are always given with a leading one.
+fn base_url(url: &str) -> Result<String, Error> {
+  Url::parse(url)?;
+  Ok(url.trim_end_matches('/').into())
+}
+
+/// Read a response body, pointing to the faulty field when it doesn't match.
+/// The body itself isn't part of the error since it can hold tokens.
+fn parse_body<T: DeserializeOwned>(body: &str) -> Result<T, Error> {
+  let deserializer = &mut serde_json::Deserializer::from_str(body);
+
+  serde_path_to_error::deserialize(deserializer).map_err(|error| {
+    Error::UnexpectedResponse {
+      path: error.path().to_string(),
+      source: error.into_inner(),
+    }
+  })
+}
+
+/// Whether a response is JSON, rather than the file that was asked.
+fn is_json(response: &Response) -> bool {
+  response
+    .headers()
+    .get(header::CONTENT_TYPE)
+    .and_then(|value| value.to_str().ok())
+    .is_some_and(|value| value.starts_with("application/json"))
+}
+
+/// Only the status of a response, to peek at it before reading the data.
+#[derive(serde::Deserialize)]
+struct ResponseStatus {
+  code: u32,
+  message: Option<String>,
 }
 
 impl RequestManager {
   pub fn new(authentication: Arc<Mutex<Authentication>>) -> Self {
-    Self { authentication }
+    Self {
+      authentication,
+      client: Client::new(),
+      user_agent: None,
+      api_url: API_URL.into(),
+      apip_url: APIP_URL.into(),
+      credential_store: None,
+    }
   }
 
-  pub async fn send<T: serde::de::DeserializeOwned + Debug>(
+  pub fn with_config(
+    authentication: Arc<Mutex<Authentication>>,
+    config: &HttpConfig,
+  ) -> Result<Self, Error> {
+    let user_agent = config
+      .user_agent_override()
+      .map(HeaderValue::from_str)
+      .transpose()?;
+
+    Ok(Self {
+      authentication,
+      client: config.build_client()?,
+      user_agent,
+      api_url: base_url(config.api_url_override().unwrap_or(API_URL))?,
+      apip_url: base_url(config.apip_url_override().unwrap_or(APIP_URL))?,
+      credential_store: None,
+    })
+  }
+
+  /// Base URL of the requests of students and families,
+  /// [`API_URL`] unless configured otherwise.
+  pub fn api_url(&self) -> &str {
+    &self.api_url
+  }
+
+  /// Base URL of the requests of teachers and staff,
+  /// [`APIP_URL`] unless configured otherwise.
+  pub fn apip_url(&self) -> &str {
+    &self.apip_url
+  }
+
+  /// Send requests to other hosts than the official ones.
+  pub fn set_base_urls(
     &mut self,
-    request: Request,
-  ) -> Result<(Option<APIResponseWrap<T>>, HeaderMap), Error> {
-    let client = Client::new();
+    api_url: &str,
+    apip_url: &str,
+  ) -> Result<(), Error> {
+    self.api_url = base_url(api_url)?;
+    self.apip_url = base_url(apip_url)?;
+    Ok(())
+  }
 
-    let response = client.execute(request).await?;
+  /// Apply the configured user agent to a request.
+  fn prepare(&self, request: &mut Request) {
+    if let Some(user_agent) = &self.user_agent {
+      request
+        .headers_mut()
+        .insert(header::USER_AGENT, user_agent.clone());
+    }
+  }
+
+  /// Send a request, nothing more.
+  async fn fetch(&self, mut request: Request) -> Result<Response, Error> {
+    self.prepare(&mut request);
+    Ok(self.client.execute(request).await?)
+  }
+
+  /// Send a request and read its body, nothing more.
+  pub(crate) async fn execute(
+    &self,
+    request: Request,
+  ) -> Result<(String, HeaderMap), Error> {
+    let response = self.fetch(request).await?;
     let headers = response.headers().clone();
-    let json = response.json::<APIResponseWrap<T>>().await;
+    let body = response.text().await?;
+
+    Ok((body, headers))
+  }
+
+  /// Send a request, when the token has expired this logs in again
+  /// with the stored access token and retries the request once.
+  ///
+  /// Unsuccessful response codes are given back as an [`Error`].
+  pub async fn send<T: DeserializeOwned>(
+    &mut self,
+    request: Request,
+  ) -> Result<(APIResponseWrap<T>, HeaderMap), Error> {
+    let retry = request.try_clone();
+    let (mut body, mut headers) = self.execute(request).await?;
+
+    if let Some(mut retry) = retry
+      && self.is_token_expired(&body)
+    {
+      self.renew_token(&mut retry).await?;
+      (body, headers) = self.execute(retry).await?;
+    }
+
+    Ok((self.read_response(&body)?, headers))
+  }
+
+  /// Log in again with the stored access token,
+  /// then give its new token to the request to retry.
+  async fn renew_token(&mut self, retry: &mut Request) -> Result<(), Error> {
+    reauthenticate(self)
+      .await
+      .map_err(|error| Error::ReauthenticationFailed(Box::new(error)))?;
+
+    if retry.headers().contains_key("X-Token") {
+      retry
+        .headers_mut()
+        .insert("X-Token", self.token()?.parse()?);
+    }
+
+    Ok(())
+  }
+
+  /// Check the code of a response then read it entirely,
+  /// keeping the token it gives for the next requests.
+  pub(crate) fn read_response<T: DeserializeOwned>(
+    &self,
+    body: &str,
+  ) -> Result<APIResponseWrap<T>, Error> {
+    let status: ResponseStatus = parse_body(body)?;
+
+    if let Some(error) = Error::from_code(status.code, status.message) {
+      return Err(error);
+    }
+
+    let json: APIResponseWrap<T> = parse_body(body)?;
+    self.store_token(json.token.as_deref());
+
+    Ok(json)
+  }
+
+  /// Whether the token has expired and can be renewed with an access token.
+  fn is_token_expired(&self, body: &str) -> bool {
+    let has_access_token =
+      self.authentication.lock().unwrap().access_token.is_some();
+
+    has_access_token
+      && serde_json::from_str::<ResponseStatus>(body)
+        .is_ok_and(|json| TOKEN_EXPIRED_CODES.contains(&json.code))
+  }
+
+  pub(crate) fn store_token(&self, token: Option<&str>) {
+    if let Some(token) = token {
+      let mut auth = self.authentication.lock().unwrap();
+      auth.token = Some(token.into());
+    }
+  }
 
-    if let Ok(json) = json {
-      if let Some(token) = json.token.as_ref() {
-        let mut auth = self.authentication.lock().unwrap();
-        auth.token = Some(token.clone());
+  /// Send a request for a file, its content is left to be read
+  /// from the returned [`Download`].
+  ///
+  /// Like [`RequestManager::send`], an expired token is renewed
+  /// and the request retried once.
+  pub async fn download(
+    &mut self,
+    request: Request,
+  ) -> Result<Download, Error> {
+    let retry = request.try_clone();
+    let mut response = self.fetch(request).await?;
+
+    // errors are sent as JSON instead of the file.
+    if is_json(&response) {
+      let body = response.text().await?;
+
+      let Some(mut retry) = retry.filter(|_| self.is_token_expired(&body))
+      else {
+        return Err(self.unexpected_json(&body));
+      };
+
+      self.renew_token(&mut retry).await?;
+      response = self.fetch(retry).await?;
+
+      if is_json(&response) {
+        let body = response.text().await?;
+        return Err(self.unexpected_json(&body));
       }
+    }
 
-      Ok((Some(json), headers))
-    } else {
-      Ok((None, headers))
+    Ok(Download::new(response))
+  }
+
+  /// Error for a JSON response received instead of a file.
+  fn unexpected_json(&self, body: &str) -> Error {
+    match self.read_response::<IgnoredAny>(body) {
+      Ok(json) => Error::Api {
+        code: json.code,
+        message: "a file was expected, got a JSON response".into(),
+      },
+      Err(error) => error,
     }
   }
+
+  /// Send a request to a data endpoint and only keep its `data` field.
+  pub async fn send_data<T: DeserializeOwned>(
+    &mut self,
+    request: Request,
+  ) -> Result<T, Error> {
+    let (json, _) = self.send::<T>(request).await?;
+    Ok(json.data)
+  }
+
+  /// Current session token, required by every data endpoint.
+  pub fn token(&self) -> Result<String, Error> {
+    let auth = self.authentication.lock().unwrap();
+    auth.token.clone().ok_or(Error::WrongLoginState())
+  }
 }
 
 pub struct RequestBuilder<T: serde::Serialize> {
   method: http::Method,
   headers: HeaderMap,
   form: Option<T>,
+  file: Option<(String, Vec<u8>)>,
   url: Url,
 }
 
 impl<T: serde::Serialize> RequestBuilder<T> {
-  pub fn new(method: http::Method, path: &str) -> Result<Self, Error> {
-    let url = Url::parse(format!("{API_URL}{path}").as_ref())?;
+  /// Request to `path` on `base_url`, usually
+  /// [`RequestManager::api_url`] or [`RequestManager::apip_url`].
+  pub fn new(
+    base_url: &str,
+    method: http::Method,
+    path: &str,
+  ) -> Result<Self, Error> {
+    let url = Url::parse(format!("{base_url}{path}").as_ref())?;
 
     let mut headers = HeaderMap::new();
     headers.insert(header::USER_AGENT, USER_AGENT.parse()?);
@@ -64,6 +308,7 @@ impl<T: serde::Serialize> RequestBuilder<T> {
       method,
       headers,
       form: None,
+      file: None,
       url,
     })
   }
@@ -73,6 +318,11 @@ impl<T: serde::Serialize> RequestBuilder<T> {
     self
   }
 
+  pub fn append_query(mut self, name: &str, value: &str) -> Self {
+    self.url.query_pairs_mut().append_pair(name, value);
+    self
+  }
+
   pub fn set_token(mut self, token: String) -> Result<Self, Error> {
     self.headers.insert("X-Token", token.parse()?);
     Ok(self)
@@ -83,11 +333,31 @@ impl<T: serde::Serialize> RequestBuilder<T> {
     self
   }
 
+  /// Send a file as `multipart/form-data`, the form is then ignored.
+  pub fn set_file(mut self, file_name: &str, content: Vec<u8>) -> Self {
+    self.file = Some((file_name.into(), content));
+    self
+  }
+
   pub fn build(self) -> Result<Request, Error> {
     let mut request = Request::new(self.method, self.url);
     *request.headers_mut() = self.headers;
 
-    if let Some(form) = self.form {
+    if let Some((file_name, content)) = self.file {
+      let form =
+        Form::new().part("file", Part::bytes(content).file_name(file_name));
+
+      request.headers_mut().insert(
+        header::CONTENT_TYPE,
+        format!("multipart/form-data; boundary={}", form.boundary()).parse()?,
+      );
+
+      // read at once rather than streamed, so the request can be retried.
+      let mut body = Vec::new();
+      form.into_reader().read_to_end(&mut body)?;
+
+      *request.body_mut() = Some(body.into());
+    } else if let Some(form) = self.form {
       let json = serde_json::to_string(&form)?;
 
       let mut params = HashMap::new();
@@ -105,3 +375,36 @@ impl<T: serde::Serialize> RequestBuilder<T> {
     Ok(request)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::parse_body;
+  use crate::{
+    api::Error,
+    definitions::{api::APIResponseWrap, responses::LoginResponse},
+  };
+  use serde_json::Value;
+  use std::fs;
+
+  #[test]
+  fn reports_malformed_bodies_without_their_content() {
+    let data = fs::read_to_string("tests/student_login.json")
+      .expect("failed to read json file");
+
+    let mut json: Value = serde_json::from_str(&data).unwrap();
+    json["token"] = "secret-token".into();
+    json["data"]["changementMDP"] = "not a boolean".into();
+
+    let result =
+      parse_body::<APIResponseWrap<LoginResponse>>(&json.to_string());
+    let Err(error) = result else {
+      panic!("malformed body has been accepted");
+    };
+
+    assert!(matches!(
+      &error,
+      Error::UnexpectedResponse { path, .. } if path == "data.changementMDP"
+    ));
+    assert!(!format!("{error:?}").contains("secret-token"));
+  }
+}
@@ -0,0 +1,25 @@
+use crate::{
+  api::{Error, RequestBuilder, RequestManager},
+  definitions::{requests::EmptyRequest, responses::SchoolLifeResponse},
+};
+
+use http::Method;
+
+/// Fetch the absences, delays, punishments, sanctions
+/// and encouragements of a student.
+pub async fn get_school_life(
+  request_manager: &mut RequestManager,
+  student_id: u32,
+) -> Result<SchoolLifeResponse, Error> {
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("/v3/eleves/{student_id}/viescolaire.awp?verbe=get"),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(EmptyRequest {})
+  .build()?;
+
+  request_manager.send_data(request).await
+}
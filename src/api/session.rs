@@ -0,0 +1,649 @@
+use crate::{
+  api::{
+    Download, Error, LoginManager, RequestManager, create_cloud_folder,
+    delete_cloud_nodes, download_cloud_file, download_document,
+    get_assigned_homework, get_cloud_folder, get_documents,
+    get_financial_situation, get_grades, get_homework_day, get_homework_range,
+    get_meal_pass_orders, get_message, get_message_folders, get_messages,
+    get_messages_in_folder, get_reservations, get_school_life,
+    get_teacher_classes, get_teacher_timetable, get_timetable,
+    get_upcoming_homework, move_messages, order_meal_pass, search_contacts,
+    send_message, set_homework_done, set_meal_reservation, set_messages_read,
+    upload_cloud_file,
+  },
+  definitions::{
+    models::{
+      Account, AccountKind, AssignedHomework, CloudNode, Contact, ContactKind,
+      Document, HomeworkDay, Lesson, MealPass, MealPassOrder, MealReservation,
+      Message, MessageBox, MessageFolder, Profile, TeacherClass, TeacherGroup,
+      TeacherProfile,
+    },
+    responses::{
+      DocumentsResponse, FinancialSituationResponse, GradesResponse,
+      MealPassOrdersResponse, MessagesResponse, ReservationsResponse,
+      SchoolLifeResponse, TeacherClassesResponse, UpcomingHomeworkResponse,
+    },
+  },
+};
+
+use chrono::NaiveDate;
+
+/// Logged in state, hands out a handle for every account and student
+/// so data endpoints don't have to be given ids and tokens by hand.
+#[derive(Debug)]
+pub struct Session {
+  login: LoginManager,
+  accounts: Vec<Account>,
+}
+
+impl Session {
+  /// Build a session from a login manager that went through
+  /// [`LoginManager::request`] successfully, 2FA included.
+  pub fn new(login: LoginManager) -> Result<Self, Error> {
+    if login.requires_2fa {
+      return Err(Error::WrongLoginState());
+    }
+
+    let accounts = login.accounts()?;
+    Ok(Self { login, accounts })
+  }
+
+  pub fn login_manager(&self) -> &LoginManager {
+    &self.login
+  }
+
+  pub fn accounts(&self) -> &[Account] {
+    &self.accounts
+  }
+
+  pub fn account_handles(&self) -> Vec<AccountHandle> {
+    self
+      .accounts
+      .iter()
+      .map(|account| AccountHandle {
+        request_manager: self.login.request_manager(),
+        account: account.clone(),
+      })
+      .collect()
+  }
+
+  /// Every student reachable from this session: the logged in student
+  /// itself, or each child of a parent account.
+  pub fn student_handles(&self) -> Vec<StudentHandle> {
+    self
+      .account_handles()
+      .iter()
+      .flat_map(AccountHandle::students)
+      .collect()
+  }
+
+  /// Every teacher or staff account of this session.
+  pub fn teacher_handles(&self) -> Vec<TeacherHandle> {
+    self
+      .account_handles()
+      .iter()
+      .filter_map(AccountHandle::teacher)
+      .collect()
+  }
+}
+
+/// Account of the session, used for everything that isn't bound
+/// to a single student, such as messaging.
+#[derive(Debug, Clone)]
+pub struct AccountHandle {
+  request_manager: RequestManager,
+  account: Account,
+}
+
+impl AccountHandle {
+  pub fn account(&self) -> &Account {
+    &self.account
+  }
+
+  /// Students this account can access.
+  pub fn students(&self) -> Vec<StudentHandle> {
+    match &self.account.profile {
+      Profile::Student(_) => vec![StudentHandle {
+        request_manager: self.request_manager.clone(),
+        id: self.account.id,
+        account_kind: self.account.kind.clone(),
+        first_name: self.account.first_name.clone(),
+        last_name: self.account.last_name.clone(),
+      }],
+      Profile::Parent(profile) => profile
+        .child
+        .iter()
+        .map(|child| StudentHandle {
+          request_manager: self.request_manager.clone(),
+          id: child.id,
+          account_kind: self.account.kind.clone(),
+          first_name: child.first_name.clone(),
+          last_name: child.last_name.clone(),
+        })
+        .collect(),
+      Profile::Teacher(_) | Profile::Other(_) => Vec::new(),
+    }
+  }
+
+  /// Teacher or staff member behind this account, if it's one.
+  pub fn teacher(&self) -> Option<TeacherHandle> {
+    let Profile::Teacher(profile) = &self.account.profile else {
+      return None;
+    };
+
+    Some(TeacherHandle {
+      request_manager: self.request_manager.clone(),
+      id: self.account.id,
+      account_kind: self.account.kind.clone(),
+      first_name: self.account.first_name.clone(),
+      last_name: self.account.last_name.clone(),
+      profile: profile.clone(),
+    })
+  }
+
+  pub async fn messages(
+    &mut self,
+    mailbox: MessageBox,
+    page: u32,
+    items_per_page: u32,
+  ) -> Result<MessagesResponse, Error> {
+    get_messages(
+      &mut self.request_manager,
+      &self.account,
+      mailbox,
+      page,
+      items_per_page,
+    )
+    .await
+  }
+
+  pub async fn messages_in_folder(
+    &mut self,
+    mailbox: MessageBox,
+    folder_id: u32,
+    page: u32,
+    items_per_page: u32,
+  ) -> Result<MessagesResponse, Error> {
+    get_messages_in_folder(
+      &mut self.request_manager,
+      &self.account,
+      mailbox,
+      folder_id,
+      page,
+      items_per_page,
+    )
+    .await
+  }
+
+  pub async fn message_folders(&mut self) -> Result<Vec<MessageFolder>, Error> {
+    get_message_folders(&mut self.request_manager, &self.account).await
+  }
+
+  pub async fn message(
+    &mut self,
+    mailbox: MessageBox,
+    message_id: u32,
+  ) -> Result<Message, Error> {
+    get_message(
+      &mut self.request_manager,
+      &self.account,
+      mailbox,
+      message_id,
+    )
+    .await
+  }
+
+  pub async fn set_messages_read(
+    &mut self,
+    message_ids: Vec<u32>,
+    read: bool,
+  ) -> Result<(), Error> {
+    set_messages_read(
+      &mut self.request_manager,
+      &self.account,
+      message_ids,
+      read,
+    )
+    .await
+  }
+
+  pub async fn move_messages(
+    &mut self,
+    message_ids: Vec<u32>,
+    folder_id: u32,
+  ) -> Result<(), Error> {
+    move_messages(
+      &mut self.request_manager,
+      &self.account,
+      message_ids,
+      folder_id,
+    )
+    .await
+  }
+
+  pub async fn search_contacts(
+    &mut self,
+    kind: ContactKind,
+    query: String,
+  ) -> Result<Vec<Contact>, Error> {
+    search_contacts(&mut self.request_manager, kind, query).await
+  }
+
+  pub async fn send_message(
+    &mut self,
+    recipients: Vec<Contact>,
+    subject: String,
+    content: String,
+  ) -> Result<(), Error> {
+    send_message(
+      &mut self.request_manager,
+      &self.account,
+      recipients,
+      subject,
+      content,
+    )
+    .await
+  }
+
+  /// Content of the cloud space, from its root or from `folder_id`.
+  pub async fn cloud_folder(
+    &mut self,
+    folder_id: Option<&str>,
+  ) -> Result<Vec<CloudNode>, Error> {
+    get_cloud_folder(&mut self.request_manager, &self.account, folder_id).await
+  }
+
+  pub async fn download_cloud_file(
+    &mut self,
+    file: &CloudNode,
+  ) -> Result<Download, Error> {
+    download_cloud_file(&mut self.request_manager, file).await
+  }
+
+  pub async fn upload_cloud_file(
+    &mut self,
+    folder: &CloudNode,
+    file_name: &str,
+    content: Vec<u8>,
+  ) -> Result<(), Error> {
+    upload_cloud_file(&mut self.request_manager, &folder.id, file_name, content)
+      .await
+  }
+
+  pub async fn create_cloud_folder(
+    &mut self,
+    parent: &CloudNode,
+    name: String,
+  ) -> Result<CloudNode, Error> {
+    create_cloud_folder(&mut self.request_manager, &self.account, parent, name)
+      .await
+  }
+
+  pub async fn delete_cloud_nodes(
+    &mut self,
+    nodes: &[CloudNode],
+  ) -> Result<(), Error> {
+    delete_cloud_nodes(&mut self.request_manager, &self.account, nodes).await
+  }
+
+  /// Balances, transactions and invoices of the family.
+  pub async fn financial_situation(
+    &mut self,
+  ) -> Result<FinancialSituationResponse, Error> {
+    get_financial_situation(&mut self.request_manager).await
+  }
+}
+
+/// Student reachable from an account, either the account itself
+/// or one of the children of a parent account.
+#[derive(Debug, Clone)]
+pub struct StudentHandle {
+  request_manager: RequestManager,
+  id: u32,
+  account_kind: AccountKind,
+  first_name: String,
+  last_name: String,
+}
+
+impl StudentHandle {
+  pub fn id(&self) -> u32 {
+    self.id
+  }
+
+  /// Kind of the account this student has been reached from.
+  pub fn account_kind(&self) -> &AccountKind {
+    &self.account_kind
+  }
+
+  pub fn first_name(&self) -> &str {
+    &self.first_name
+  }
+
+  pub fn last_name(&self) -> &str {
+    &self.last_name
+  }
+
+  pub async fn grades(
+    &mut self,
+    school_year: Option<String>,
+  ) -> Result<GradesResponse, Error> {
+    get_grades(&mut self.request_manager, self.id, school_year).await
+  }
+
+  pub async fn homework_day(
+    &mut self,
+    date: NaiveDate,
+  ) -> Result<HomeworkDay, Error> {
+    get_homework_day(&mut self.request_manager, self.id, date).await
+  }
+
+  pub async fn homework_range(
+    &mut self,
+    start: NaiveDate,
+    end: NaiveDate,
+  ) -> Result<Vec<HomeworkDay>, Error> {
+    get_homework_range(&mut self.request_manager, self.id, start, end).await
+  }
+
+  pub async fn upcoming_homework(
+    &mut self,
+  ) -> Result<UpcomingHomeworkResponse, Error> {
+    get_upcoming_homework(&mut self.request_manager, self.id).await
+  }
+
+  pub async fn set_homework_done(
+    &mut self,
+    homework_id: u32,
+    done: bool,
+  ) -> Result<(), Error> {
+    set_homework_done(&mut self.request_manager, self.id, homework_id, done)
+      .await
+  }
+
+  pub async fn timetable(
+    &mut self,
+    start: NaiveDate,
+    end: NaiveDate,
+  ) -> Result<Vec<Lesson>, Error> {
+    get_timetable(&mut self.request_manager, self.id, start, end).await
+  }
+
+  pub async fn school_life(&mut self) -> Result<SchoolLifeResponse, Error> {
+    get_school_life(&mut self.request_manager, self.id).await
+  }
+
+  pub async fn documents(
+    &mut self,
+    school_year: Option<String>,
+  ) -> Result<DocumentsResponse, Error> {
+    get_documents(&mut self.request_manager, self.id, school_year).await
+  }
+
+  pub async fn download_document(
+    &mut self,
+    document: &Document,
+  ) -> Result<Download, Error> {
+    download_document(&mut self.request_manager, document).await
+  }
+
+  /// Canteen reservations of the week starting on `start`.
+  pub async fn reservations(
+    &mut self,
+    start: NaiveDate,
+  ) -> Result<ReservationsResponse, Error> {
+    get_reservations(&mut self.request_manager, self.id, start).await
+  }
+
+  pub async fn set_meal_reservation(
+    &mut self,
+    meal: &MealReservation,
+    reserved: bool,
+  ) -> Result<(), Error> {
+    set_meal_reservation(&mut self.request_manager, self.id, meal, reserved)
+      .await
+  }
+
+  pub async fn meal_pass_orders(
+    &mut self,
+  ) -> Result<MealPassOrdersResponse, Error> {
+    get_meal_pass_orders(&mut self.request_manager, self.id).await
+  }
+
+  pub async fn order_meal_pass(
+    &mut self,
+    pass: &MealPass,
+    quantity: u32,
+  ) -> Result<MealPassOrder, Error> {
+    order_meal_pass(&mut self.request_manager, self.id, pass, quantity).await
+  }
+}
+
+/// Teacher or staff member logged in, their requests go through
+/// [`APIP_URL`](crate::api::APIP_URL).
+#[derive(Debug, Clone)]
+pub struct TeacherHandle {
+  request_manager: RequestManager,
+  id: u32,
+  account_kind: AccountKind,
+  first_name: String,
+  last_name: String,
+  profile: TeacherProfile,
+}
+
+impl TeacherHandle {
+  pub fn id(&self) -> u32 {
+    self.id
+  }
+
+  pub fn account_kind(&self) -> &AccountKind {
+    &self.account_kind
+  }
+
+  pub fn first_name(&self) -> &str {
+    &self.first_name
+  }
+
+  pub fn last_name(&self) -> &str {
+    &self.last_name
+  }
+
+  pub fn profile(&self) -> &TeacherProfile {
+    &self.profile
+  }
+
+  /// Classes taught, as given when logging in.
+  pub fn classes(&self) -> &[TeacherClass] {
+    &self.profile.classes
+  }
+
+  /// Groups taught, as given when logging in.
+  pub fn groups(&self) -> &[TeacherGroup] {
+    &self.profile.groups
+  }
+
+  /// Fetch the classes and groups taught, as they are now.
+  pub async fn fetch_classes(
+    &mut self,
+  ) -> Result<TeacherClassesResponse, Error> {
+    get_teacher_classes(&mut self.request_manager, &self.account_kind, self.id)
+      .await
+  }
+
+  pub async fn timetable(
+    &mut self,
+    start: NaiveDate,
+    end: NaiveDate,
+  ) -> Result<Vec<Lesson>, Error> {
+    get_teacher_timetable(
+      &mut self.request_manager,
+      &self.account_kind,
+      self.id,
+      start,
+      end,
+    )
+    .await
+  }
+
+  pub async fn assigned_homework(
+    &mut self,
+    start: NaiveDate,
+    end: NaiveDate,
+  ) -> Result<Vec<AssignedHomework>, Error> {
+    get_assigned_homework(
+      &mut self.request_manager,
+      &self.account_kind,
+      self.id,
+      start,
+      end,
+    )
+    .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::Session;
+  use crate::{definitions::models::AccountKind, mock};
+  use chrono::NaiveDate;
+
+  #[tokio::test]
+  async fn gives_a_handle_to_the_logged_in_student() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let session = Session::new(login).unwrap();
+
+    assert_eq!(session.account_handles().len(), 1);
+    assert!(session.teacher_handles().is_empty());
+
+    let students = session.student_handles();
+    assert_eq!(students.len(), 1);
+    assert_eq!(students[0].id(), 4179);
+    assert_eq!(students[0].account_kind(), &AccountKind::Student);
+  }
+
+  #[tokio::test]
+  async fn gives_a_handle_to_each_child_of_a_family() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::FAMILY_USERNAME).await.unwrap();
+    let session = Session::new(login).unwrap();
+
+    let mut students = session.student_handles();
+    let ids = students
+      .iter()
+      .map(|student| student.id())
+      .collect::<Vec<_>>();
+    assert_eq!(ids, [4193, 4179, 4191]);
+    assert_eq!(students[1].first_name(), "Pierre");
+    assert!(
+      students
+        .iter()
+        .all(|student| student.account_kind() == &AccountKind::Family)
+    );
+
+    // calls are made for the child, with the token of the session.
+    let token = session.login_manager().request_manager().token().unwrap();
+    students[1].school_life().await.unwrap();
+
+    let received = server.received();
+    let request = received.last().unwrap();
+    assert_eq!(request.path, "/v3/eleves/4179/viescolaire.awp");
+    assert_eq!(request.headers["x-token"], token);
+  }
+
+  #[tokio::test]
+  async fn teachers_go_through_apip() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::TEACHER_USERNAME).await.unwrap();
+
+    let received = server.received();
+    assert!(!received.is_empty());
+    assert!(
+      received
+        .iter()
+        .all(|request| request.host == mock::Host::Apip)
+    );
+
+    let session = Session::new(login).unwrap();
+    assert!(session.student_handles().is_empty());
+
+    let mut teacher = session.teacher_handles().pop().unwrap();
+    assert_eq!(teacher.account_kind(), &AccountKind::Teacher);
+    assert_eq!(teacher.classes().len(), 2);
+
+    let classes = teacher.fetch_classes().await.unwrap();
+    assert_eq!(classes.groups[1].class_id, Some(12));
+
+    let request = server.received().pop().unwrap();
+    assert_eq!(request.host, mock::Host::Apip);
+    assert_eq!(request.path, "/v3/enseignants/107/classes.awp");
+  }
+
+  #[tokio::test]
+  async fn teacher_handle_fetches_timetable_and_homework() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::TEACHER_USERNAME).await.unwrap();
+    let session = Session::new(login).unwrap();
+    let mut teacher = session.teacher_handles().pop().unwrap();
+
+    let start = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap();
+    let end = NaiveDate::from_ymd_opt(2025, 1, 17).unwrap();
+
+    let lessons = teacher.timetable(start, end).await.unwrap();
+    assert_eq!(lessons.len(), 2);
+
+    let homework = teacher.assigned_homework(start, end).await.unwrap();
+    assert_eq!(homework[0].target, "LATIN 4EME");
+
+    let paths = server
+      .received()
+      .into_iter()
+      .rev()
+      .take(2)
+      .map(|request| request.path)
+      .collect::<Vec<_>>();
+    assert_eq!(
+      paths,
+      [
+        "/v3/enseignants/107/cahierdetexte.awp",
+        "/v3/enseignants/107/emploidutemps.awp"
+      ]
+    );
+  }
+
+  #[tokio::test]
+  async fn staff_members_use_their_own_path() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STAFF_USERNAME).await.unwrap();
+    let session = Session::new(login).unwrap();
+
+    let mut staff = session.teacher_handles().pop().unwrap();
+    assert_eq!(staff.account_kind(), &AccountKind::Staff);
+    staff.fetch_classes().await.unwrap();
+
+    let request = server.received().pop().unwrap();
+    assert_eq!(request.host, mock::Host::Apip);
+    assert_eq!(request.path, "/v3/personnels/218/classes.awp");
+  }
+
+  #[tokio::test]
+  async fn teachers_are_unknown_to_the_api_host() {
+    let server = mock::MockServer::start().await.unwrap();
+    let mut login = crate::api::LoginManager::from_credentials(
+      mock::TEACHER_USERNAME.into(),
+      mock::PASSWORD.into(),
+      None,
+    )
+    .with_http_config(&server.http_config())
+    .unwrap();
+
+    assert!(login.request().await.is_err());
+  }
+
+  #[tokio::test]
+  async fn refuses_unfinished_double_auth() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::DOUBLE_AUTH_USERNAME).await.unwrap();
+
+    assert!(Session::new(login).is_err());
+  }
+}
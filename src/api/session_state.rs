@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  api::Authentication,
+  definitions::responses::{DoubleAuthSolveResponse, LoginResponse},
+};
+
+/// Current version of the [`SessionState`] format.
+pub const SESSION_STATE_VERSION: u32 = 1;
+
+/// Everything needed to restore a login later on,
+/// without going through the double authentication again.
+///
+/// It contains the tokens of the account, keep it somewhere safe.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SessionState {
+  /// Format version, see [`SESSION_STATE_VERSION`].
+  pub version: u32,
+  pub authentication: Authentication,
+  pub login_response: Option<LoginResponse>,
+  /// Trusted device identifiers given after solving the double auth.
+  pub double_auth: Option<DoubleAuthSolveResponse>,
+}
@@ -0,0 +1,188 @@
+use crate::{
+  api::{Error, RequestBuilder, RequestManager},
+  definitions::{
+    models::{AccountKind, AssignedHomework, Lesson},
+    requests::{AssignedHomeworkRequest, EmptyRequest, TimetableRequest},
+    responses::TeacherClassesResponse,
+  },
+};
+
+use chrono::NaiveDate;
+use http::Method;
+
+/// Teachers and staff members each have their own path segment,
+/// `enseignants` and `personnels`, other accounts are refused.
+fn teacher_path(kind: &AccountKind, teacher_id: u32) -> Result<String, Error> {
+  let owner = kind
+    .path_prefix()
+    .filter(|_| kind.uses_apip())
+    .ok_or_else(|| Error::UnsupportedAccountKind(kind.code().into()))?;
+
+  Ok(format!("/v3/{owner}/{teacher_id}"))
+}
+
+/// Fetch the classes and groups a teacher or staff member is in charge of.
+pub async fn get_teacher_classes(
+  request_manager: &mut RequestManager,
+  kind: &AccountKind,
+  teacher_id: u32,
+) -> Result<TeacherClassesResponse, Error> {
+  let request = RequestBuilder::new(
+    request_manager.apip_url(),
+    Method::POST,
+    &format!("{}/classes.awp?verbe=get", teacher_path(kind, teacher_id)?),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(EmptyRequest {})
+  .build()?;
+
+  request_manager.send_data(request).await
+}
+
+/// Fetch the lessons of a teacher or staff member
+/// between `start` and `end` (both included).
+pub async fn get_teacher_timetable(
+  request_manager: &mut RequestManager,
+  kind: &AccountKind,
+  teacher_id: u32,
+  start: NaiveDate,
+  end: NaiveDate,
+) -> Result<Vec<Lesson>, Error> {
+  let request = RequestBuilder::new(
+    request_manager.apip_url(),
+    Method::POST,
+    &format!(
+      "{}/emploidutemps.awp?verbe=get",
+      teacher_path(kind, teacher_id)?
+    ),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(TimetableRequest {
+    start_date: start,
+    end_date: end,
+    with_gaps: false,
+  })
+  .build()?;
+
+  request_manager.send_data(request).await
+}
+
+/// Fetch the homework a teacher has given, due between `start`
+/// and `end` (both included).
+pub async fn get_assigned_homework(
+  request_manager: &mut RequestManager,
+  kind: &AccountKind,
+  teacher_id: u32,
+  start: NaiveDate,
+  end: NaiveDate,
+) -> Result<Vec<AssignedHomework>, Error> {
+  let request = RequestBuilder::new(
+    request_manager.apip_url(),
+    Method::POST,
+    &format!(
+      "{}/cahierdetexte.awp?verbe=get",
+      teacher_path(kind, teacher_id)?
+    ),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(AssignedHomeworkRequest {
+    start_date: start,
+    end_date: end,
+  })
+  .build()?;
+
+  request_manager.send_data(request).await
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{get_assigned_homework, get_teacher_timetable};
+  use crate::{
+    api::Error,
+    definitions::models::AccountKind,
+    mock::{self, Host},
+  };
+  use chrono::NaiveDate;
+  use serde_json::Value;
+
+  #[tokio::test]
+  async fn can_get_teacher_timetable() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::TEACHER_USERNAME).await.unwrap();
+    let mut request_manager = login.request_manager();
+
+    let day = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap();
+    let lessons = get_teacher_timetable(
+      &mut request_manager,
+      &AccountKind::Teacher,
+      107,
+      day,
+      day,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(lessons.len(), 2);
+
+    let request = server.received().pop().unwrap();
+    assert_eq!(request.host, Host::Apip);
+    assert_eq!(request.path, "/v3/enseignants/107/emploidutemps.awp");
+
+    let form = request.form::<Value>().unwrap();
+    assert_eq!(form["dateDebut"], "2025-01-13");
+    assert_eq!(form["dateFin"], "2025-01-13");
+  }
+
+  #[tokio::test]
+  async fn can_get_assigned_homework() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STAFF_USERNAME).await.unwrap();
+    let mut request_manager = login.request_manager();
+
+    let start = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap();
+    let end = NaiveDate::from_ymd_opt(2025, 1, 24).unwrap();
+    let homework = get_assigned_homework(
+      &mut request_manager,
+      &AccountKind::Staff,
+      218,
+      start,
+      end,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(homework[1].documents.len(), 1);
+
+    let request = server.received().pop().unwrap();
+    assert_eq!(request.host, Host::Apip);
+    assert_eq!(request.path, "/v3/personnels/218/cahierdetexte.awp");
+
+    let form = request.form::<Value>().unwrap();
+    assert_eq!(form["dateDebut"], "2025-01-13");
+    assert_eq!(form["dateFin"], "2025-01-24");
+  }
+
+  #[tokio::test]
+  async fn refuses_students() {
+    let server = mock::MockServer::start().await.unwrap();
+    let login = server.login(mock::STUDENT_USERNAME).await.unwrap();
+    let mut request_manager = login.request_manager();
+
+    let day = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap();
+    let sent = server.received().len();
+    let result = get_teacher_timetable(
+      &mut request_manager,
+      &AccountKind::Student,
+      4179,
+      day,
+      day,
+    )
+    .await;
+
+    assert!(matches!(result, Err(Error::UnsupportedAccountKind(_))));
+    assert_eq!(server.received().len(), sent);
+  }
+}
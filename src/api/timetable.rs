@@ -0,0 +1,31 @@
+use crate::{
+  api::{Error, RequestBuilder, RequestManager},
+  definitions::{models::Lesson, requests::TimetableRequest},
+};
+
+use chrono::NaiveDate;
+use http::Method;
+
+/// Fetch the lessons of a student between `start` and `end` (both included).
+pub async fn get_timetable(
+  request_manager: &mut RequestManager,
+  student_id: u32,
+  start: NaiveDate,
+  end: NaiveDate,
+) -> Result<Vec<Lesson>, Error> {
+  let request = RequestBuilder::new(
+    request_manager.api_url(),
+    Method::POST,
+    &format!("/v3/E/{student_id}/emploidutemps.awp?verbe=get"),
+  )?
+  .append_version()
+  .set_token(request_manager.token()?)?
+  .set_form(TimetableRequest {
+    start_date: start,
+    end_date: end,
+    with_gaps: false,
+  })
+  .build()?;
+
+  request_manager.send_data(request).await
+}
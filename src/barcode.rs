@@ -0,0 +1,207 @@
+//! Render canteen badges, see [`Account::canteen_badge_number`].
+//!
+//! [`Account::canteen_badge_number`]: crate::definitions::models::Account::canteen_badge_number
+
+use crate::api::Error;
+
+/// Characters of Code 39 by group, a group shares the position
+/// of its wide space and a character is known by its place in it.
+const GROUPS: [&str; 4] =
+  ["1234567890", "ABCDEFGHIJ", "KLMNOPQRST", "UVWXYZ-. *"];
+
+/// Wide bars of each place within a group, two out of five.
+const BARS: [[bool; 5]; 10] = [
+  [true, false, false, false, true],
+  [false, true, false, false, true],
+  [true, true, false, false, false],
+  [false, false, true, false, true],
+  [true, false, true, false, false],
+  [false, true, true, false, false],
+  [false, false, false, true, true],
+  [true, false, false, true, false],
+  [false, true, false, true, false],
+  [false, false, true, true, false],
+];
+
+/// Position of the wide space for each group, in the same order.
+const WIDE_SPACES: [usize; 4] = [1, 2, 3, 0];
+
+/// Width of a wide element, in narrow ones.
+const WIDE: usize = 3;
+
+/// Blank space around the barcode, in narrow elements.
+const QUIET_ZONE: usize = 10;
+
+/// Code 39 barcode, as read by the canteen scanners.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Barcode {
+  /// Every narrow element from left to right, `true` for a bar.
+  modules: Vec<bool>,
+}
+
+impl Barcode {
+  /// Encode `data` as Code 39, lowercase letters are uppercased.
+  pub fn code39(data: &str) -> Result<Self, Error> {
+    let data = data.to_uppercase();
+    if data.contains('*') {
+      return Err(Error::InvalidBarcode(data));
+    }
+
+    let mut modules = Vec::new();
+
+    for (index, character) in format!("*{data}*").chars().enumerate() {
+      if index > 0 {
+        modules.push(false);
+      }
+
+      let widths =
+        widths(character).ok_or_else(|| Error::InvalidBarcode(data.clone()))?;
+
+      for (element, is_wide) in widths.into_iter().enumerate() {
+        let width = if is_wide { WIDE } else { 1 };
+        modules.extend(std::iter::repeat_n(element % 2 == 0, width));
+      }
+    }
+
+    Ok(Self { modules })
+  }
+
+  /// Width of the barcode in narrow elements, quiet zones included.
+  pub fn width(&self) -> usize {
+    self.modules.len() + 2 * QUIET_ZONE
+  }
+
+  /// SVG document, one unit per narrow element.
+  pub fn to_svg(&self, height: u32) -> String {
+    let width = self.width();
+    let mut svg = format!(
+      "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+      width=\"{width}\" height=\"{height}\" \
+      viewBox=\"0 0 {width} {height}\" shape-rendering=\"crispEdges\">\
+      <rect width=\"{width}\" height=\"{height}\" fill=\"#fff\"/>"
+    );
+
+    for (start, length) in self.bars() {
+      svg.push_str(&format!(
+        "<rect x=\"{}\" width=\"{length}\" height=\"{height}\"/>",
+        start + QUIET_ZONE
+      ));
+    }
+
+    svg.push_str("</svg>");
+    svg
+  }
+
+  /// Grayscale PNG image, `scale` pixels wide for each narrow element.
+  pub fn to_png(&self, scale: u32, height: u32) -> Result<Vec<u8>, Error> {
+    let row = std::iter::repeat_n(false, QUIET_ZONE)
+      .chain(self.modules.iter().copied())
+      .chain(std::iter::repeat_n(false, QUIET_ZONE))
+      .flat_map(|is_bar| {
+        let pixel = if is_bar { 0x00 } else { 0xff };
+        std::iter::repeat_n(pixel, scale as usize)
+      })
+      .collect::<Vec<u8>>();
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, row.len() as u32, height);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&row.repeat(height as usize))?;
+    writer.finish()?;
+
+    Ok(png)
+  }
+
+  /// Start and length of every bar.
+  fn bars(&self) -> Vec<(usize, usize)> {
+    let mut bars: Vec<(usize, usize)> = Vec::new();
+
+    for (index, is_bar) in self.modules.iter().enumerate() {
+      match bars.last_mut() {
+        Some((start, length)) if *is_bar && *start + *length == index => {
+          *length += 1;
+        }
+        _ if *is_bar => bars.push((index, 1)),
+        _ => {}
+      }
+    }
+
+    bars
+  }
+}
+
+/// Whether each of the nine elements of `character` is wide,
+/// alternating bars and spaces starting with a bar.
+fn widths(character: char) -> Option<[bool; 9]> {
+  let mut widths = [false; 9];
+
+  // `$`, `/`, `+` and `%` are made of three wide spaces.
+  let narrow_space = match character {
+    '$' => Some(3),
+    '/' => Some(2),
+    '+' => Some(1),
+    '%' => Some(0),
+    _ => None,
+  };
+
+  if let Some(narrow_space) = narrow_space {
+    for space in (0..4).filter(|space| *space != narrow_space) {
+      widths[space * 2 + 1] = true;
+    }
+
+    return Some(widths);
+  }
+
+  let (group, place) =
+    GROUPS.iter().enumerate().find_map(|(group, characters)| {
+      characters.find(character).map(|place| (group, place))
+    })?;
+
+  for (bar, is_wide) in BARS[place].into_iter().enumerate() {
+    widths[bar * 2] = is_wide;
+  }
+
+  widths[WIDE_SPACES[group] * 2 + 1] = true;
+  Some(widths)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{Barcode, widths};
+  use crate::api::Error;
+
+  /// Pattern written with `n` for narrow and `w` for wide elements.
+  fn pattern(character: char) -> String {
+    widths(character)
+      .unwrap()
+      .iter()
+      .map(|is_wide| if *is_wide { 'w' } else { 'n' })
+      .collect()
+  }
+
+  #[test]
+  fn encodes_code39_characters() {
+    assert_eq!(pattern('*'), "nwnnwnwnn");
+    assert_eq!(pattern('0'), "nnnwwnwnn");
+    assert_eq!(pattern('A'), "wnnnnwnnw");
+    assert_eq!(pattern('K'), "wnnnnnnww");
+    assert_eq!(pattern('$'), "nwnwnwnnn");
+    assert_eq!(pattern('%'), "nnnwnwnwn");
+  }
+
+  #[test]
+  fn renders_badge_number() {
+    let barcode = Barcode::code39("237629").unwrap();
+
+    // 8 characters of 15 elements, separated by 7 narrow spaces.
+    assert_eq!(barcode.width(), 8 * 15 + 7 + 20);
+    assert_eq!(barcode.to_svg(40).matches("<rect x=").count(), 8 * 5);
+    assert!(barcode.to_png(2, 40).unwrap().starts_with(b"\x89PNG"));
+
+    let result = Barcode::code39("eleve||4179");
+    assert!(matches!(result, Err(Error::InvalidBarcode(_))));
+  }
+}
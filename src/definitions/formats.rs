@@ -0,0 +1,187 @@
+//! Helpers to read the loosely typed values sent by EcoleDirecte.
+
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serializer, de::Error as _};
+use serde_json::Value;
+use std::str::FromStr;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrText {
+  Number(f64),
+  Text(String),
+}
+
+/// Reads a french formatted decimal such as `"14,5"`.
+///
+/// Empty strings and placeholders (`"Abs"`, `"Disp"`, ...) become `None`.
+pub fn french_decimal<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  Ok(match Option::<NumberOrText>::deserialize(deserializer)? {
+    Some(NumberOrText::Number(number)) => Some(number),
+    Some(NumberOrText::Text(text)) => parse_french_decimal(&text),
+    None => None,
+  })
+}
+
+pub(crate) fn parse_french_decimal(text: &str) -> Option<f64> {
+  text.trim().replace(',', ".").parse().ok()
+}
+
+/// Reads a whole number sent either as a number or as a string such as
+/// `"15"`, empty strings become `None`.
+pub fn optional_u32<'de, D>(deserializer: D) -> Result<Option<u32>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  #[derive(Deserialize)]
+  #[serde(untagged)]
+  enum WholeNumber {
+    Number(u32),
+    Text(String),
+  }
+
+  match Option::<WholeNumber>::deserialize(deserializer)? {
+    Some(WholeNumber::Number(number)) => Ok(Some(number)),
+    Some(WholeNumber::Text(text)) if text.trim().is_empty() => Ok(None),
+    Some(WholeNumber::Text(text)) => text
+      .trim()
+      .parse()
+      .map(Some)
+      .map_err(|_| D::Error::custom(format!("invalid number \"{text}\""))),
+    None => Ok(None),
+  }
+}
+
+/// Reads an amount of money such as `"1 234,50 €"` or `-12.5`,
+/// without losing precision. Empty amounts are zero.
+pub fn french_amount<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let text = match Option::<Value>::deserialize(deserializer)? {
+    Some(Value::Number(number)) => number.to_string(),
+    Some(Value::String(text)) => text,
+    None => return Ok(Decimal::ZERO),
+    Some(value) => {
+      return Err(D::Error::custom(format!("invalid amount {value}")));
+    }
+  };
+
+  parse_french_amount(&text)
+    .ok_or_else(|| D::Error::custom(format!("invalid amount \"{text}\"")))
+}
+
+pub(crate) fn parse_french_amount(text: &str) -> Option<Decimal> {
+  let text = text
+    .chars()
+    .filter(|c| !c.is_whitespace() && *c != '€')
+    .map(|c| if c == ',' { '.' } else { c })
+    .collect::<String>();
+
+  if text.is_empty() {
+    return Some(Decimal::ZERO);
+  }
+
+  Decimal::from_str(&text)
+    .or_else(|_| Decimal::from_scientific(&text))
+    .ok()
+}
+
+/// Reads a `YYYY-MM-DD` date, empty strings become `None`.
+pub fn optional_date<'de, D>(
+  deserializer: D,
+) -> Result<Option<NaiveDate>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  match Option::<String>::deserialize(deserializer)?.as_deref() {
+    None | Some("") => Ok(None),
+    Some(text) => NaiveDate::parse_from_str(text, "%Y-%m-%d")
+      .map(Some)
+      .map_err(D::Error::custom),
+  }
+}
+
+/// Reads a `YYYY-MM-DD HH:MM` date time, seconds are optional.
+pub fn date_time<'de, D>(deserializer: D) -> Result<NaiveDateTime, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let text = String::deserialize(deserializer)?;
+
+  parse_date_time(&text)
+    .ok_or_else(|| D::Error::custom(format!("invalid date time \"{text}\"")))
+}
+
+/// Reads a `YYYY-MM-DD HH:MM` date time, empty strings become `None`.
+pub fn optional_date_time<'de, D>(
+  deserializer: D,
+) -> Result<Option<NaiveDateTime>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  match Option::<String>::deserialize(deserializer)?.as_deref() {
+    None | Some("") => Ok(None),
+    Some(text) => parse_date_time(text)
+      .map(Some)
+      .ok_or_else(|| D::Error::custom(format!("invalid date time \"{text}\""))),
+  }
+}
+
+pub(crate) fn parse_date_time(text: &str) -> Option<NaiveDateTime> {
+  NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S")
+    .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M"))
+    .ok()
+}
+
+/// Reads a flag sent as a boolean, a `0`/`1` number or a `"0"`/`"1"` string.
+pub fn flag<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  #[derive(Deserialize)]
+  #[serde(untagged)]
+  enum Flag {
+    Bool(bool),
+    Number(i64),
+    Text(String),
+  }
+
+  Ok(match Flag::deserialize(deserializer)? {
+    Flag::Bool(value) => value,
+    Flag::Number(value) => value != 0,
+    Flag::Text(value) => matches!(value.as_str(), "1" | "true"),
+  })
+}
+
+/// Reads a comma separated list such as `"2022-2023,2023-2024"`.
+pub fn comma_separated<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let text = String::deserialize(deserializer)?;
+
+  Ok(
+    text
+      .split(',')
+      .map(str::trim)
+      .filter(|item| !item.is_empty())
+      .map(String::from)
+      .collect(),
+  )
+}
+
+/// Writes a list back the way [`comma_separated`] reads it.
+pub fn join_comma_separated<S>(
+  items: &[String],
+  serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+  S: Serializer,
+{
+  serializer.serialize_str(&items.join(","))
+}
@@ -1,4 +1,5 @@
 pub mod api;
+pub mod formats;
 pub mod models;
 pub mod requests;
 pub mod responses;
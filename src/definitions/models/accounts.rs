@@ -1,7 +1,92 @@
-use crate::definitions::models::{Class, Profile};
-use serde::Deserialize;
+use crate::{
+  api::REDACTED,
+  definitions::models::{
+    Class, IndividualParameters, Module, ModuleKind, ModuleParams, Profile,
+    ProfileSeed,
+  },
+};
+use serde::{
+  Deserialize, Deserializer, Serialize, Serializer,
+  de::{
+    DeserializeSeed, Error as _, IntoDeserializer, MapAccess, Visitor,
+    value::MapAccessDeserializer,
+  },
+};
+use serde_json::{Map, Value};
+use std::fmt;
 
-#[derive(Debug, Clone, Deserialize)]
+/// Kind of account, the tag of its [`Profile`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum AccountKind {
+  /// `"E"`
+  Student,
+  /// `"1"`, parents and legal guardians.
+  Family,
+  /// `"P"`
+  Teacher,
+  /// `"A"`, administrative staff.
+  Staff,
+  Unknown(String),
+}
+
+impl AccountKind {
+  /// Code of the account kind, as sent by EcoleDirecte.
+  pub fn code(&self) -> &str {
+    match self {
+      Self::Student => "E",
+      Self::Family => "1",
+      Self::Teacher => "P",
+      Self::Staff => "A",
+      Self::Unknown(code) => code,
+    }
+  }
+
+  /// Segment of the API paths for accounts of this kind,
+  /// such as `eleves` in `/v3/eleves/{id}/messages.awp`.
+  pub fn path_prefix(&self) -> Option<&'static str> {
+    match self {
+      Self::Student => Some("eleves"),
+      Self::Family => Some("familles"),
+      Self::Teacher => Some("enseignants"),
+      Self::Staff => Some("personnels"),
+      Self::Unknown(_) => None,
+    }
+  }
+
+  /// Whether the account goes through [`APIP_URL`](crate::api::APIP_URL)
+  /// rather than the host of students and families.
+  pub fn uses_apip(&self) -> bool {
+    matches!(self, Self::Teacher | Self::Staff)
+  }
+}
+
+impl From<&str> for AccountKind {
+  fn from(code: &str) -> Self {
+    match code {
+      "E" => Self::Student,
+      "1" => Self::Family,
+      "P" => Self::Teacher,
+      "A" => Self::Staff,
+      other => Self::Unknown(other.into()),
+    }
+  }
+}
+
+impl From<String> for AccountKind {
+  fn from(code: String) -> Self {
+    code.as_str().into()
+  }
+}
+
+impl From<AccountKind> for String {
+  fn from(kind: AccountKind) -> Self {
+    kind.code().into()
+  }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(remote = "Self")]
 pub struct Account {
   #[serde(rename = "idLogin")]
   pub id_login: u32,
@@ -10,7 +95,7 @@ pub struct Account {
   #[serde(rename = "identifiant")]
   pub username: String,
   #[serde(rename = "typeCompte")]
-  pub kind: String,
+  pub kind: AccountKind,
   #[serde(rename = "codeOgec")]
   pub ogec_code: String,
   #[serde(rename = "main")]
@@ -40,14 +125,158 @@ pub struct Account {
   pub socket_token: String,
   #[serde(rename = "accessToken")]
   pub access_token: String,
-  #[serde(skip)]
-  pub modules: Vec<String>,
-  #[serde(skip, rename = "parametresIndividuels")]
-  pub individual_parameters: String,
+  #[serde(default)]
+  pub modules: Vec<Module>,
+  #[serde(rename = "parametresIndividuels", default)]
+  pub individual_parameters: IndividualParameters,
+  /// Read according to `typeCompte`, see [`Profile::deserialize_as`].
+  #[serde(skip_deserializing, default = "no_profile")]
   pub profile: Profile,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+fn no_profile() -> Profile {
+  Profile::Other(Map::new())
+}
+
+impl<'de> Deserialize<'de> for Account {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    deserializer.deserialize_map(AccountVisitor)
+  }
+}
+
+struct AccountVisitor;
+
+impl<'de> Visitor<'de> for AccountVisitor {
+  type Value = Account;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("an account")
+  }
+
+  fn visit_map<A>(self, map: A) -> Result<Account, A::Error>
+  where
+    A: MapAccess<'de>,
+  {
+    let mut fields = AccountFields {
+      map,
+      kind: None,
+      reading_kind: false,
+      profile: None,
+      buffered_profile: None,
+    };
+    let mut account =
+      Account::deserialize(MapAccessDeserializer::new(&mut fields))?;
+
+    account.profile = match (fields.profile, fields.buffered_profile) {
+      (Some(profile), _) => profile,
+      (None, Some(value)) => Profile::deserialize_as(&account.kind, value)
+        .map_err(A::Error::custom)?,
+      (None, None) => return Err(A::Error::missing_field("profile")),
+    };
+
+    Ok(account)
+  }
+}
+
+/// Fields of an account, minus its profile which is read according to
+/// `typeCompte` as it comes along.
+struct AccountFields<A> {
+  map: A,
+  kind: Option<AccountKind>,
+  /// Whether the last key was `typeCompte`.
+  reading_kind: bool,
+  profile: Option<Profile>,
+  /// Profile that came before `typeCompte`, read once the kind is known.
+  buffered_profile: Option<Value>,
+}
+
+impl<'de, A> MapAccess<'de> for AccountFields<A>
+where
+  A: MapAccess<'de>,
+{
+  type Error = A::Error;
+
+  fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, A::Error>
+  where
+    K: DeserializeSeed<'de>,
+  {
+    while let Some(key) = self.map.next_key::<String>()? {
+      if key != "profile" {
+        self.reading_kind = key == "typeCompte";
+        return seed.deserialize(key.into_deserializer()).map(Some);
+      }
+
+      match &self.kind {
+        Some(kind) => {
+          self.profile = Some(self.map.next_value_seed(ProfileSeed(kind))?)
+        }
+        None => self.buffered_profile = Some(self.map.next_value()?),
+      }
+    }
+
+    Ok(None)
+  }
+
+  fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, A::Error>
+  where
+    V: DeserializeSeed<'de>,
+  {
+    if !std::mem::take(&mut self.reading_kind) {
+      return self.map.next_value_seed(seed);
+    }
+
+    let code: String = self.map.next_value()?;
+    self.kind = Some(code.as_str().into());
+    seed.deserialize(code.into_deserializer())
+  }
+}
+
+impl Serialize for Account {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    Account::serialize(self, serializer)
+  }
+}
+
+impl fmt::Debug for Account {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Account")
+      .field("id_login", &self.id_login)
+      .field("id", &self.id)
+      .field("uid", &self.uid)
+      .field("username", &self.username)
+      .field("kind", &self.kind)
+      .field("ogec_code", &self.ogec_code)
+      .field("is_main", &self.is_main)
+      .field("last_connection", &self.last_connection)
+      .field("civility", &self.civility)
+      .field("first_name", &self.first_name)
+      .field("prefix", &self.prefix)
+      .field("last_name", &self.last_name)
+      .field("email", &self.email)
+      .field("is_primary_school", &self.is_primary_school)
+      .field("school_name", &self.school_name)
+      .field("school_logo_path", &self.school_logo_path)
+      .field("school_agenda_color_hex", &self.school_agenda_color_hex)
+      .field(
+        "le_robert_online_dictionary",
+        &self.le_robert_online_dictionary,
+      )
+      .field("socket_token", &REDACTED)
+      .field("access_token", &REDACTED)
+      .field("modules", &self.modules)
+      .field("individual_parameters", &self.individual_parameters)
+      .field("profile", &self.profile)
+      .finish()
+  }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ChildAccount {
   pub id: u32,
   #[serde(rename = "prenom")]
@@ -68,8 +297,49 @@ pub struct ChildAccount {
   pub school_real_id: String,
   #[serde(rename = "isPrimaire")]
   pub is_primary_school: bool,
-  #[serde(skip)]
-  pub modules: Vec<String>,
+  #[serde(default)]
+  pub modules: Vec<Module>,
   #[serde(rename = "classe")]
   pub class: Class,
 }
+
+/// Whether `kind` is part of `modules` and enabled by the school.
+fn has_module(modules: &[Module], kind: ModuleKind) -> bool {
+  modules
+    .iter()
+    .any(|module| module.kind == kind && module.is_enabled)
+}
+
+/// Badge number of the enabled `CANTINE_BARCODE` module, if any.
+fn canteen_badge_number(modules: &[Module]) -> Option<&str> {
+  modules.iter().find_map(|module| match &module.params {
+    ModuleParams::CanteenBarcode(params)
+      if module.is_enabled && !params.number().is_empty() =>
+    {
+      Some(params.number())
+    }
+    _ => None,
+  })
+}
+
+impl Account {
+  pub fn has_module(&self, kind: ModuleKind) -> bool {
+    has_module(&self.modules, kind)
+  }
+
+  /// Number printed on the canteen badge, to be rendered as a barcode.
+  pub fn canteen_badge_number(&self) -> Option<&str> {
+    canteen_badge_number(&self.modules)
+  }
+}
+
+impl ChildAccount {
+  pub fn has_module(&self, kind: ModuleKind) -> bool {
+    has_module(&self.modules, kind)
+  }
+
+  /// Number printed on the canteen badge, to be rendered as a barcode.
+  pub fn canteen_badge_number(&self) -> Option<&str> {
+    canteen_badge_number(&self.modules)
+  }
+}
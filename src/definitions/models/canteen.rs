@@ -0,0 +1,162 @@
+use crate::definitions::formats::{
+  flag, french_amount, optional_date, optional_date_time,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Meals that can be reserved on a day.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "RawReservationDay")]
+pub struct ReservationDay {
+  pub date: NaiveDate,
+  pub meals: Vec<MealReservation>,
+}
+
+#[derive(Deserialize)]
+struct RawReservationDay {
+  date: NaiveDate,
+  #[serde(rename = "repas", default)]
+  meals: Vec<RawMealReservation>,
+}
+
+impl From<RawReservationDay> for ReservationDay {
+  fn from(day: RawReservationDay) -> Self {
+    let meals = day
+      .meals
+      .into_iter()
+      .map(|meal| MealReservation {
+        date: day.date,
+        slot: meal.slot,
+        label: meal.label,
+        is_reserved: meal.is_reserved,
+        is_locked: meal.is_locked,
+        deadline: meal.deadline,
+      })
+      .collect();
+
+    Self {
+      date: day.date,
+      meals,
+    }
+  }
+}
+
+/// Meal of a [`ReservationDay`], only read as part of it since
+/// EcoleDirecte gives the date of the day and not of each meal.
+#[derive(Debug, Clone)]
+pub struct MealReservation {
+  pub date: NaiveDate,
+  pub slot: MealSlot,
+  pub label: String,
+  pub is_reserved: bool,
+  /// Locked by the school, the reservation can't be changed anymore.
+  pub is_locked: bool,
+  /// Last moment the reservation can be changed.
+  pub deadline: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize)]
+struct RawMealReservation {
+  #[serde(rename = "code")]
+  slot: MealSlot,
+  #[serde(rename = "libelle")]
+  label: String,
+  #[serde(rename = "reserve", deserialize_with = "flag")]
+  is_reserved: bool,
+  #[serde(rename = "verrouille", default, deserialize_with = "flag")]
+  is_locked: bool,
+  #[serde(
+    rename = "dateLimite",
+    default,
+    deserialize_with = "optional_date_time"
+  )]
+  deadline: Option<NaiveDateTime>,
+}
+
+impl MealReservation {
+  /// Whether the reservation can still be changed at `now`,
+  /// given in the time zone of the school.
+  pub fn is_open_at(&self, now: NaiveDateTime) -> bool {
+    !self.is_locked && self.deadline.is_none_or(|deadline| now <= deadline)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum MealSlot {
+  /// `"PDJ"`
+  Breakfast,
+  /// `"DEJ"`
+  Lunch,
+  /// `"DIN"`
+  Dinner,
+  Unknown(String),
+}
+
+impl MealSlot {
+  /// Code of the meal slot, as sent by EcoleDirecte.
+  pub fn code(&self) -> &str {
+    match self {
+      Self::Breakfast => "PDJ",
+      Self::Lunch => "DEJ",
+      Self::Dinner => "DIN",
+      Self::Unknown(code) => code,
+    }
+  }
+}
+
+impl From<&str> for MealSlot {
+  fn from(code: &str) -> Self {
+    match code {
+      "PDJ" => Self::Breakfast,
+      "DEJ" => Self::Lunch,
+      "DIN" => Self::Dinner,
+      other => Self::Unknown(other.into()),
+    }
+  }
+}
+
+impl From<String> for MealSlot {
+  fn from(code: String) -> Self {
+    code.as_str().into()
+  }
+}
+
+impl From<MealSlot> for String {
+  fn from(slot: MealSlot) -> Self {
+    slot.code().into()
+  }
+}
+
+/// Meal pass that can be ordered, such as a pack of ten meals.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MealPass {
+  pub id: u32,
+  #[serde(rename = "libelle")]
+  pub name: String,
+  #[serde(rename = "prix", default, deserialize_with = "french_amount")]
+  pub price: Decimal,
+  #[serde(rename = "quantiteMax", default)]
+  pub max_quantity: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MealPassOrder {
+  pub id: u32,
+  #[serde(rename = "libelle")]
+  pub name: String,
+  #[serde(
+    rename = "dateCommande",
+    default,
+    deserialize_with = "optional_date"
+  )]
+  pub date: Option<NaiveDate>,
+  #[serde(rename = "quantite")]
+  pub quantity: u32,
+  #[serde(rename = "montant", default, deserialize_with = "french_amount")]
+  pub amount: Decimal,
+  /// State of the order, as labelled by the school.
+  #[serde(rename = "etat", default)]
+  pub status: String,
+}
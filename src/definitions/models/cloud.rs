@@ -0,0 +1,68 @@
+use crate::definitions::formats::optional_date_time;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// File or folder of a cloud space.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CloudNode {
+  /// Path of the node, such as `\E\1234\Devoirs\rapport.pdf`.
+  pub id: String,
+  #[serde(rename = "libelle")]
+  pub name: String,
+  #[serde(rename = "type")]
+  pub kind: CloudNodeKind,
+  /// Size in bytes, of the whole content for folders.
+  #[serde(rename = "taille", default)]
+  pub size: u64,
+  #[serde(default, deserialize_with = "optional_date_time")]
+  pub date: Option<NaiveDateTime>,
+  /// Content of a folder, only when it has been loaded.
+  #[serde(default)]
+  pub children: Vec<CloudNode>,
+  /// Whether `children` has been filled by EcoleDirecte.
+  #[serde(rename = "isLoaded", default)]
+  pub is_loaded: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CloudNodeKind {
+  Folder,
+  File,
+  #[serde(other)]
+  Unknown,
+}
+
+impl CloudNode {
+  pub fn is_folder(&self) -> bool {
+    self.kind == CloudNodeKind::Folder
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{CloudNode, CloudNodeKind};
+  use crate::definitions::api::APIResponseWrap;
+  use std::fs;
+
+  #[test]
+  fn can_read_cloud_tree() {
+    let data =
+      fs::read_to_string("tests/cloud.json").expect("failed to read json file");
+
+    let parsed: APIResponseWrap<Vec<CloudNode>> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    let root = &parsed.data[0];
+    assert!(root.is_folder() && root.is_loaded);
+
+    let file = root
+      .children
+      .iter()
+      .find(|node| node.kind == CloudNodeKind::File)
+      .expect("no file in the cloud");
+
+    assert_eq!(file.size, 48213);
+    assert!(file.date.is_some());
+  }
+}
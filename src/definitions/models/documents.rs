@@ -0,0 +1,18 @@
+use crate::definitions::formats::optional_date;
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+/// Document made available to a student by the school.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Document {
+  pub id: u32,
+  #[serde(rename = "libelle")]
+  pub name: String,
+  #[serde(rename = "idEleve", default)]
+  pub student_id: Option<u32>,
+  #[serde(default, deserialize_with = "optional_date")]
+  pub date: Option<NaiveDate>,
+  /// Kind of file, given back when downloading the document.
+  #[serde(rename = "type")]
+  pub kind: String,
+}
@@ -0,0 +1,65 @@
+use crate::definitions::formats::{flag, french_amount, optional_date};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+/// Account held by the school for a service such as the canteen,
+/// the boarding school or the trips.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FinancialAccount {
+  pub id: u32,
+  #[serde(rename = "libelle")]
+  pub name: String,
+  /// Kind of service, `"CANT"` for the canteen for example.
+  #[serde(rename = "codeCompte", default)]
+  pub code: String,
+  /// Student the account is for, if it's not shared by the family.
+  #[serde(rename = "idEleve", default)]
+  pub student_id: Option<u32>,
+  /// Negative when money is owed to the school.
+  #[serde(rename = "solde", deserialize_with = "french_amount")]
+  pub balance: Decimal,
+  #[serde(rename = "ecritures", default)]
+  pub transactions: Vec<Transaction>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Transaction {
+  pub id: u32,
+  #[serde(default, deserialize_with = "optional_date")]
+  pub date: Option<NaiveDate>,
+  #[serde(rename = "libelle")]
+  pub label: String,
+  /// Negative for a payment made to the school.
+  #[serde(rename = "montant", deserialize_with = "french_amount")]
+  pub amount: Decimal,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Invoice {
+  pub id: u32,
+  #[serde(rename = "libelle")]
+  pub label: String,
+  #[serde(default, deserialize_with = "optional_date")]
+  pub date: Option<NaiveDate>,
+  #[serde(
+    rename = "dateEcheance",
+    default,
+    deserialize_with = "optional_date"
+  )]
+  pub due_date: Option<NaiveDate>,
+  #[serde(rename = "montant", deserialize_with = "french_amount")]
+  pub amount: Decimal,
+  /// Part of the amount that hasn't been paid yet.
+  #[serde(rename = "resteAPayer", default, deserialize_with = "french_amount")]
+  pub remaining: Decimal,
+  #[serde(rename = "payee", default, deserialize_with = "flag")]
+  pub is_paid: bool,
+}
+
+impl Invoice {
+  /// Whether some of the invoice is still to be paid.
+  pub fn is_outstanding(&self) -> bool {
+    !self.is_paid && self.remaining > Decimal::ZERO
+  }
+}
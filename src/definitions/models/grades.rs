@@ -0,0 +1,162 @@
+use crate::definitions::formats::{french_decimal, parse_french_decimal};
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Period {
+  #[serde(rename = "idPeriode")]
+  pub id: String,
+  #[serde(rename = "codePeriode")]
+  pub code: String,
+  #[serde(rename = "periode")]
+  pub name: String,
+  #[serde(rename = "annuel")]
+  pub is_yearly: bool,
+  #[serde(rename = "examenBlanc")]
+  pub is_mock_exam: bool,
+  #[serde(rename = "cloture")]
+  pub is_closed: bool,
+  #[serde(rename = "dateDebut")]
+  pub start_date: NaiveDate,
+  #[serde(rename = "dateFin")]
+  pub end_date: NaiveDate,
+  #[serde(rename = "ensembleMatieres")]
+  pub overview: PeriodOverview,
+}
+
+/// General averages and per-subject averages of a period.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PeriodOverview {
+  #[serde(
+    rename = "moyenneGenerale",
+    default,
+    deserialize_with = "french_decimal"
+  )]
+  pub average: Option<f64>,
+  #[serde(
+    rename = "moyenneClasse",
+    default,
+    deserialize_with = "french_decimal"
+  )]
+  pub class_average: Option<f64>,
+  #[serde(rename = "moyenneMin", default, deserialize_with = "french_decimal")]
+  pub class_min: Option<f64>,
+  #[serde(rename = "moyenneMax", default, deserialize_with = "french_decimal")]
+  pub class_max: Option<f64>,
+  #[serde(rename = "disciplines", default)]
+  pub subjects: Vec<Subject>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Subject {
+  pub id: u32,
+  #[serde(rename = "codeMatiere")]
+  pub code: String,
+  #[serde(rename = "codeSousMatiere")]
+  pub sub_code: String,
+  #[serde(rename = "discipline")]
+  pub name: String,
+  #[serde(rename = "coef", default, deserialize_with = "french_decimal")]
+  pub coefficient: Option<f64>,
+  #[serde(rename = "moyenne", default, deserialize_with = "french_decimal")]
+  pub average: Option<f64>,
+  #[serde(
+    rename = "moyenneClasse",
+    default,
+    deserialize_with = "french_decimal"
+  )]
+  pub class_average: Option<f64>,
+  #[serde(rename = "moyenneMin", default, deserialize_with = "french_decimal")]
+  pub class_min: Option<f64>,
+  #[serde(rename = "moyenneMax", default, deserialize_with = "french_decimal")]
+  pub class_max: Option<f64>,
+  /// Whether this entry groups other subjects rather than being graded.
+  #[serde(rename = "groupeMatiere")]
+  pub is_group: bool,
+  #[serde(rename = "sousMatiere")]
+  pub is_sub_subject: bool,
+  #[serde(rename = "professeurs", default)]
+  pub teachers: Vec<SubjectTeacher>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubjectTeacher {
+  pub id: u32,
+  #[serde(rename = "nom")]
+  pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Grade {
+  pub id: u32,
+  #[serde(rename = "devoir")]
+  pub name: String,
+  #[serde(rename = "codePeriode")]
+  pub period_code: String,
+  #[serde(rename = "codeMatiere")]
+  pub subject_code: String,
+  #[serde(rename = "libelleMatiere")]
+  pub subject_name: String,
+  #[serde(rename = "typeDevoir")]
+  pub kind: String,
+  #[serde(rename = "valeur")]
+  pub value: GradeValue,
+  #[serde(rename = "noteSur", default, deserialize_with = "french_decimal")]
+  pub out_of: Option<f64>,
+  #[serde(rename = "coef", default, deserialize_with = "french_decimal")]
+  pub coefficient: Option<f64>,
+  /// Grade that doesn't count in the averages.
+  #[serde(rename = "nonSignificatif")]
+  pub is_optional: bool,
+  pub date: NaiveDate,
+  #[serde(rename = "dateSaisie")]
+  pub entry_date: NaiveDate,
+  #[serde(rename = "commentaire")]
+  pub comment: String,
+  #[serde(
+    rename = "moyenneClasse",
+    default,
+    deserialize_with = "french_decimal"
+  )]
+  pub class_average: Option<f64>,
+  #[serde(rename = "minClasse", default, deserialize_with = "french_decimal")]
+  pub class_min: Option<f64>,
+  #[serde(rename = "maxClasse", default, deserialize_with = "french_decimal")]
+  pub class_max: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GradeValue {
+  Number(f64),
+  /// `"Abs"`, the student was absent.
+  Absent,
+  /// `"Disp"`, the student was exempted.
+  Exempted,
+  /// `"NE"`, the work has not been graded.
+  NotGraded,
+  /// Any other non-numeric value, such as letter grades.
+  Other(String),
+}
+
+impl From<&str> for GradeValue {
+  fn from(value: &str) -> Self {
+    match value.trim() {
+      "Abs" => Self::Absent,
+      "Disp" => Self::Exempted,
+      "NE" => Self::NotGraded,
+      other => parse_french_decimal(other)
+        .map(Self::Number)
+        .unwrap_or_else(|| Self::Other(other.into())),
+    }
+  }
+}
+
+impl<'de> Deserialize<'de> for GradeValue {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let value = String::deserialize(deserializer)?;
+    Ok(value.as_str().into())
+  }
+}
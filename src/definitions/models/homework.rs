@@ -0,0 +1,92 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde_with::{base64::Base64, serde_as};
+
+/// Everything planned in the textbook for a single day.
+#[derive(Debug, Clone, Deserialize)]
+pub struct HomeworkDay {
+  pub date: NaiveDate,
+  #[serde(rename = "matieres", default)]
+  pub entries: Vec<HomeworkEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HomeworkEntry {
+  pub id: u32,
+  #[serde(rename = "matiere")]
+  pub subject: String,
+  #[serde(rename = "codeMatiere")]
+  pub subject_code: String,
+  #[serde(rename = "nomProf")]
+  pub teacher: String,
+  #[serde(rename = "interrogation")]
+  pub is_test: bool,
+  #[serde(rename = "aFaire")]
+  pub assignment: Option<Assignment>,
+  #[serde(rename = "contenuDeSeance")]
+  pub session_content: Option<SessionContent>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+pub struct Assignment {
+  #[serde(rename = "idDevoir")]
+  pub id: u32,
+  /// HTML description of the work to do.
+  #[serde_as(as = "Base64")]
+  #[serde(rename = "contenu", default)]
+  pub content: String,
+  #[serde(rename = "donneLe")]
+  pub given_on: NaiveDate,
+  #[serde(rename = "effectue")]
+  pub is_done: bool,
+  #[serde(rename = "rendreEnLigne")]
+  pub submit_online: bool,
+  #[serde(default)]
+  pub documents: Vec<Attachment>,
+}
+
+/// What has been done in class during the session.
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+pub struct SessionContent {
+  /// HTML summary of the session.
+  #[serde_as(as = "Base64")]
+  #[serde(rename = "contenu", default)]
+  pub content: String,
+  #[serde(default)]
+  pub documents: Vec<Attachment>,
+}
+
+/// File attached to an EcoleDirecte item, such as homework or a message.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Attachment {
+  pub id: u32,
+  #[serde(rename = "libelle")]
+  pub name: String,
+  #[serde(rename = "taille", default)]
+  pub size: u64,
+  #[serde(rename = "type")]
+  pub kind: String,
+}
+
+/// Assignment listed in the upcoming homework, without its content.
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpcomingHomework {
+  #[serde(rename = "idDevoir")]
+  pub id: u32,
+  #[serde(rename = "matiere")]
+  pub subject: String,
+  #[serde(rename = "codeMatiere")]
+  pub subject_code: String,
+  #[serde(rename = "donneLe")]
+  pub given_on: NaiveDate,
+  #[serde(rename = "effectue")]
+  pub is_done: bool,
+  #[serde(rename = "interrogation")]
+  pub is_test: bool,
+  #[serde(rename = "rendreEnLigne", default)]
+  pub submit_online: bool,
+  #[serde(rename = "documentsAFaire", default)]
+  pub has_documents: bool,
+}
@@ -0,0 +1,53 @@
+use crate::definitions::formats::{flag, optional_u32};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Preferences of an account, set by the user or by the school.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct IndividualParameters {
+  #[serde(rename = "isQrcode", deserialize_with = "flag")]
+  pub is_qr_code: bool,
+  #[serde(rename = "accessibiliteVisuelle", deserialize_with = "flag")]
+  pub visual_accessibility: bool,
+  #[serde(rename = "zoomPage", deserialize_with = "flag")]
+  pub page_zoom: bool,
+  #[serde(rename = "checkAuthentificationSecure", deserialize_with = "flag")]
+  pub check_secure_authentication: bool,
+  #[serde(rename = "typeSaisieNotesDefaut")]
+  pub default_grades_entry_kind: String,
+  /// Default view of the homework, empty when the user never picked one.
+  #[serde(rename = "typeViewCDTDefaut")]
+  pub default_homework_view: String,
+  #[serde(
+    rename = "nbJoursMaxRenduDevoirCDT",
+    deserialize_with = "optional_u32"
+  )]
+  pub max_homework_submission_days: Option<u32>,
+  #[serde(rename = "blocPMAccueil", deserialize_with = "flag")]
+  pub show_pm_block_on_home: bool,
+  #[serde(rename = "blocActuAccueil", deserialize_with = "flag")]
+  pub show_news_block_on_home: bool,
+  #[serde(rename = "modeCalculLSU")]
+  pub lsu_computation_mode: String,
+  /// Thresholds between the four mastery levels of the LSU
+  /// (the national school report), when they're computed from grades.
+  #[serde(
+    rename = "lsuPoilDansLaMainBorne1",
+    deserialize_with = "optional_u32"
+  )]
+  pub lsu_mastery_bound_1: Option<u32>,
+  #[serde(
+    rename = "lsuPoilDansLaMainBorne2",
+    deserialize_with = "optional_u32"
+  )]
+  pub lsu_mastery_bound_2: Option<u32>,
+  #[serde(
+    rename = "lsuPoilDansLaMainBorne3",
+    deserialize_with = "optional_u32"
+  )]
+  pub lsu_mastery_bound_3: Option<u32>,
+  /// Every key this crate doesn't know about yet.
+  #[serde(flatten)]
+  pub other: Map<String, Value>,
+}
@@ -0,0 +1,101 @@
+use crate::definitions::{formats::date_time, models::Attachment};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_with::{base64::Base64, serde_as};
+
+/// Mailbox a message can be listed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageBox {
+  Received,
+  Sent,
+  Archived,
+  Draft,
+}
+
+impl MessageBox {
+  /// Value of the `typeRecuperation` query parameter.
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Received => "received",
+      Self::Sent => "sent",
+      Self::Archived => "archived",
+      Self::Draft => "draft",
+    }
+  }
+}
+
+/// Custom folder created by the user to sort its messages.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageFolder {
+  pub id: u32,
+  #[serde(rename = "libelle")]
+  pub name: String,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+pub struct Message {
+  pub id: u32,
+  /// `"received"` or `"send"`.
+  #[serde(rename = "mtype")]
+  pub kind: String,
+  pub subject: String,
+  /// HTML body, only filled when reading a single message.
+  #[serde_as(as = "Base64")]
+  #[serde(default)]
+  pub content: String,
+  #[serde(deserialize_with = "date_time")]
+  pub date: NaiveDateTime,
+  pub read: bool,
+  #[serde(rename = "idClasseur", default)]
+  pub folder_id: u32,
+  #[serde(default)]
+  pub answered: bool,
+  #[serde(default)]
+  pub transferred: bool,
+  pub from: Option<MessageContact>,
+  #[serde(default)]
+  pub to: Vec<MessageContact>,
+  #[serde(default)]
+  pub files: Vec<Attachment>,
+}
+
+/// Sender or recipient as displayed on a message.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageContact {
+  pub id: u32,
+  pub name: String,
+  /// `"P"` for teachers, `"A"` for staff, `"E"` for students, ...
+  pub role: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContactKind {
+  Teachers,
+  Staff,
+}
+
+impl ContactKind {
+  /// Path segment of the contacts endpoint.
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Teachers => "professeurs",
+      Self::Staff => "personnels",
+    }
+  }
+}
+
+/// Someone that can receive messages, as returned by a contact search.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Contact {
+  pub id: u32,
+  #[serde(rename = "civilite", default)]
+  pub civility: String,
+  #[serde(rename = "prenom", default)]
+  pub first_name: String,
+  #[serde(rename = "particule", default)]
+  pub prefix: String,
+  #[serde(rename = "nom", default)]
+  pub last_name: String,
+  pub role: String,
+}
@@ -1,5 +1,17 @@
 automod::dir!("src/definitions/models");
 
 pub use accounts::*;
+pub use canteen::*;
 pub use class::*;
+pub use cloud::*;
+pub use documents::*;
+pub use finances::*;
+pub use grades::*;
+pub use homework::*;
+pub use individual_parameters::*;
+pub use messages::*;
+pub use modules::*;
 pub use profiles::*;
+pub use school_life::*;
+pub use teachers::*;
+pub use timetable::*;
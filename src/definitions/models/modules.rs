@@ -0,0 +1,285 @@
+use crate::definitions::formats::{
+  comma_separated, flag, join_comma_separated,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Feature enabled (or not) by the school for an account.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(from = "RawModule", into = "RawModule")]
+pub struct Module {
+  pub kind: ModuleKind,
+  pub is_enabled: bool,
+  /// Position of the module in the official app menu.
+  pub order: u32,
+  /// Number of unread items, shown as a badge in the official app.
+  pub badge: u32,
+  pub params: ModuleParams,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModuleKind {
+  News,
+  Homework,
+  CanteenBarcode,
+  Correspondence,
+  Cloud,
+  MealPassOrders,
+  Account,
+  ContactDetails,
+  Documents,
+  StudentDocuments,
+  Enrollment,
+  Forms,
+  Timetable,
+  Invoices,
+  ClassJournal,
+  ClassLife,
+  Textbooks,
+  Messaging,
+  PaymentMethod,
+  Grades,
+  OnlinePayment,
+  Quizzes,
+  Reservations,
+  FinancialSituation,
+  InternshipTracking,
+  SchoolLife,
+  TeacherAppointments,
+  HeadTeacherMeetings,
+  LearningBook,
+  Unknown(String),
+}
+
+impl ModuleKind {
+  /// Code of the module, as sent by EcoleDirecte.
+  pub fn code(&self) -> &str {
+    match self {
+      Self::News => "ACTUALITES",
+      Self::Homework => "CAHIER_DE_TEXTES",
+      Self::CanteenBarcode => "CANTINE_BARCODE",
+      Self::Correspondence => "CARNET_CORRESPONDANCE",
+      Self::Cloud => "CLOUD",
+      Self::MealPassOrders => "COMMANDE_PASSAGE",
+      Self::Account => "COMPTE",
+      Self::ContactDetails => "COORDONNEES",
+      Self::Documents => "DOCUMENTS",
+      Self::StudentDocuments => "DOCUMENTS_ELEVE",
+      Self::Enrollment => "DOSSIER_INSCRIPTION",
+      Self::Forms => "EDFORMS",
+      Self::Timetable => "EDT",
+      Self::Invoices => "FACTURES",
+      Self::ClassJournal => "JOURNAL_DE_CLASSE",
+      Self::ClassLife => "VIE_DE_LA_CLASSE",
+      Self::Textbooks => "MANUELS_SCOLAIRES",
+      Self::Messaging => "MESSAGERIE",
+      Self::PaymentMethod => "MODE_DE_REGLEMENT",
+      Self::Grades => "NOTES",
+      Self::OnlinePayment => "PAIEMENT_EN_LIGNE",
+      Self::Quizzes => "QCM",
+      Self::Reservations => "RESERVATIONS",
+      Self::FinancialSituation => "SITUATION_FINANCIERE",
+      Self::InternshipTracking => "SUIVI_STAGE",
+      Self::SchoolLife => "VIE_SCOLAIRE",
+      Self::TeacherAppointments => "RDVPP",
+      Self::HeadTeacherMeetings => "REUNIONS_PP",
+      Self::LearningBook => "LIVRET_APPRENTISSAGE",
+      Self::Unknown(code) => code,
+    }
+  }
+}
+
+impl From<&str> for ModuleKind {
+  fn from(code: &str) -> Self {
+    match code {
+      "ACTUALITES" => Self::News,
+      "CAHIER_DE_TEXTES" => Self::Homework,
+      "CANTINE_BARCODE" => Self::CanteenBarcode,
+      "CARNET_CORRESPONDANCE" => Self::Correspondence,
+      "CLOUD" => Self::Cloud,
+      "COMMANDE_PASSAGE" => Self::MealPassOrders,
+      "COMPTE" => Self::Account,
+      "COORDONNEES" => Self::ContactDetails,
+      "DOCUMENTS" => Self::Documents,
+      "DOCUMENTS_ELEVE" => Self::StudentDocuments,
+      "DOSSIER_INSCRIPTION" => Self::Enrollment,
+      "EDFORMS" => Self::Forms,
+      "EDT" => Self::Timetable,
+      "FACTURES" => Self::Invoices,
+      "JOURNAL_DE_CLASSE" => Self::ClassJournal,
+      "VIE_DE_LA_CLASSE" => Self::ClassLife,
+      "MANUELS_SCOLAIRES" => Self::Textbooks,
+      "MESSAGERIE" => Self::Messaging,
+      "MODE_DE_REGLEMENT" => Self::PaymentMethod,
+      "NOTES" => Self::Grades,
+      "PAIEMENT_EN_LIGNE" => Self::OnlinePayment,
+      "QCM" => Self::Quizzes,
+      "RESERVATIONS" => Self::Reservations,
+      "SITUATION_FINANCIERE" => Self::FinancialSituation,
+      "SUIVI_STAGE" => Self::InternshipTracking,
+      "VIE_SCOLAIRE" => Self::SchoolLife,
+      "RDVPP" => Self::TeacherAppointments,
+      "REUNIONS_PP" => Self::HeadTeacherMeetings,
+      "LIVRET_APPRENTISSAGE" => Self::LearningBook,
+      other => Self::Unknown(other.into()),
+    }
+  }
+}
+
+/// Parameters of a module, typed for the modules this crate knows about.
+#[derive(Debug, Clone)]
+pub enum ModuleParams {
+  CanteenBarcode(CanteenBarcodeParams),
+  Messaging(MessagingParams),
+  Homework(HomeworkParams),
+  Documents(DocumentsParams),
+  /// Parameters of any other module, or of a known module
+  /// when they don't match the expected shape.
+  Other(Map<String, Value>),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CanteenBarcodeParams {
+  /// Raw badge, such as `eleve||4179||237629`.
+  #[serde(rename = "numeroBadge")]
+  pub badge_number: String,
+}
+
+impl CanteenBarcodeParams {
+  /// Number of the badge, the last part of [`Self::badge_number`].
+  pub fn number(&self) -> &str {
+    self.badge_number.rsplit("||").next().unwrap_or_default()
+  }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MessagingParams {
+  #[serde(rename = "isActif", default, deserialize_with = "flag")]
+  pub is_active: bool,
+  #[serde(
+    rename = "canParentsLireMessagesEnfants",
+    default,
+    deserialize_with = "flag"
+  )]
+  pub parents_can_read_children_messages: bool,
+  #[serde(rename = "destAdmin", default, deserialize_with = "flag")]
+  pub can_write_to_staff: bool,
+  #[serde(rename = "destProf", default, deserialize_with = "flag")]
+  pub can_write_to_teachers: bool,
+  #[serde(rename = "destEleve", default, deserialize_with = "flag")]
+  pub can_write_to_students: bool,
+  #[serde(rename = "destFamille", default, deserialize_with = "flag")]
+  pub can_write_to_families: bool,
+  #[serde(rename = "messagerieApiVersion", default)]
+  pub api_version: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HomeworkParams {
+  #[serde(rename = "compteRenduSeance", default, deserialize_with = "flag")]
+  pub session_content: bool,
+  #[serde(
+    rename = "compteRenduSeancePrevisionnel",
+    default,
+    deserialize_with = "flag"
+  )]
+  pub planned_session_content: bool,
+  #[serde(rename = "isCDTPrimaire", default, deserialize_with = "flag")]
+  pub is_primary_school: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DocumentsParams {
+  #[serde(rename = "DocumentsNotesActif", default, deserialize_with = "flag")]
+  pub grades: bool,
+  #[serde(rename = "DocumentsVSActif", default, deserialize_with = "flag")]
+  pub school_life: bool,
+  #[serde(
+    rename = "DocumentsAdministratifActif",
+    default,
+    deserialize_with = "flag"
+  )]
+  pub administrative: bool,
+  #[serde(
+    rename = "DocumentsFactureActif",
+    default,
+    deserialize_with = "flag"
+  )]
+  pub invoices: bool,
+  /// School years that can still be browsed, such as `"2023-2024"`.
+  #[serde(
+    rename = "AnneeArchive",
+    default,
+    deserialize_with = "comma_separated",
+    serialize_with = "join_comma_separated"
+  )]
+  pub archived_years: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct RawModule {
+  code: String,
+  enable: bool,
+  #[serde(rename = "ordre", default)]
+  order: u32,
+  #[serde(default)]
+  badge: u32,
+  #[serde(default)]
+  params: Map<String, Value>,
+}
+
+impl From<RawModule> for Module {
+  fn from(raw: RawModule) -> Self {
+    let kind = ModuleKind::from(raw.code.as_str());
+    let value = Value::Object(raw.params.clone());
+
+    let params = match kind {
+      ModuleKind::CanteenBarcode => serde_json::from_value(value)
+        .map(ModuleParams::CanteenBarcode)
+        .ok(),
+      ModuleKind::Messaging => serde_json::from_value(value)
+        .map(ModuleParams::Messaging)
+        .ok(),
+      ModuleKind::Homework => serde_json::from_value(value)
+        .map(ModuleParams::Homework)
+        .ok(),
+      ModuleKind::Documents | ModuleKind::StudentDocuments => {
+        serde_json::from_value(value)
+          .map(ModuleParams::Documents)
+          .ok()
+      }
+      _ => None,
+    };
+
+    Self {
+      kind,
+      is_enabled: raw.enable,
+      order: raw.order,
+      badge: raw.badge,
+      params: params.unwrap_or(ModuleParams::Other(raw.params)),
+    }
+  }
+}
+
+impl From<Module> for RawModule {
+  fn from(module: Module) -> Self {
+    let params = match module.params {
+      ModuleParams::CanteenBarcode(params) => serde_json::to_value(params),
+      ModuleParams::Messaging(params) => serde_json::to_value(params),
+      ModuleParams::Homework(params) => serde_json::to_value(params),
+      ModuleParams::Documents(params) => serde_json::to_value(params),
+      ModuleParams::Other(params) => Ok(Value::Object(params)),
+    };
+
+    Self {
+      code: module.kind.code().into(),
+      enable: module.is_enabled,
+      order: module.order,
+      badge: module.badge,
+      params: match params {
+        Ok(Value::Object(params)) => params,
+        _ => Map::new(),
+      },
+    }
+  }
+}
@@ -1,7 +1,10 @@
-use crate::definitions::models::{ChildAccount, Class};
-use serde::Deserialize;
+use crate::definitions::models::{
+  AccountKind, ChildAccount, Class, TeacherProfile,
+};
+use serde::{Deserialize, Deserializer, Serialize, de::DeserializeSeed};
+use serde_json::{Map, Value};
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ParentProfile {
   pub email: String,
   #[serde(rename = "telPortable")]
@@ -12,7 +15,7 @@ pub struct ParentProfile {
   pub child: Vec<ChildAccount>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct StudentProfile {
   #[serde(rename = "sexe")]
   pub gender: String,
@@ -36,9 +39,57 @@ pub struct StudentProfile {
   pub class: Option<Class>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// Profile of an account.
+///
+/// Accounts read it according to their [`AccountKind`],
+/// on its own the variant is guessed from the fields of the profile.
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum Profile {
   Parent(ParentProfile),
   Student(StudentProfile),
+  /// Teachers and staff members.
+  Teacher(TeacherProfile),
+  /// Profile of any other kind of account, left as is.
+  Other(Map<String, Value>),
+}
+
+impl Profile {
+  /// Reads the profile of an account of the given kind.
+  pub fn deserialize_as<'de, D>(
+    kind: &AccountKind,
+    deserializer: D,
+  ) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    match kind {
+      AccountKind::Family => {
+        ParentProfile::deserialize(deserializer).map(Self::Parent)
+      }
+      AccountKind::Student => {
+        StudentProfile::deserialize(deserializer).map(Self::Student)
+      }
+      AccountKind::Teacher | AccountKind::Staff => {
+        TeacherProfile::deserialize(deserializer).map(Self::Teacher)
+      }
+      AccountKind::Unknown(_) => {
+        Map::deserialize(deserializer).map(Self::Other)
+      }
+    }
+  }
+}
+
+/// Reads a [`Profile`] according to the kind of its account.
+pub(crate) struct ProfileSeed<'a>(pub &'a AccountKind);
+
+impl<'de> DeserializeSeed<'de> for ProfileSeed<'_> {
+  type Value = Profile;
+
+  fn deserialize<D>(self, deserializer: D) -> Result<Profile, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    Profile::deserialize_as(self.0, deserializer)
+  }
 }
@@ -0,0 +1,76 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer};
+
+/// Absence, delay, punishment, sanction or encouragement of a student.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SchoolLifeEvent {
+  pub id: u32,
+  #[serde(rename = "typeElement")]
+  pub kind: SchoolLifeEventKind,
+  pub date: NaiveDate,
+  /// Human readable date given by EcoleDirecte, contains the hours.
+  #[serde(rename = "displayDate")]
+  pub display_date: String,
+  #[serde(rename = "libelle")]
+  pub label: String,
+  #[serde(rename = "motif")]
+  pub reason: String,
+  #[serde(rename = "justifie")]
+  pub is_justified: bool,
+  #[serde(rename = "par", default)]
+  pub given_by: String,
+  #[serde(rename = "commentaire", default)]
+  pub comment: String,
+  /// Work the student has to do for a punishment.
+  #[serde(rename = "aFaire", default)]
+  pub work_to_do: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchoolLifeEventKind {
+  Absence,
+  Delay,
+  Punishment,
+  Sanction,
+  Encouragement,
+  Other(String),
+}
+
+impl From<&str> for SchoolLifeEventKind {
+  fn from(value: &str) -> Self {
+    match value {
+      "Absence" => Self::Absence,
+      "Retard" => Self::Delay,
+      "Punition" => Self::Punishment,
+      "Sanction" => Self::Sanction,
+      "Encouragement" => Self::Encouragement,
+      other => Self::Other(other.into()),
+    }
+  }
+}
+
+impl<'de> Deserialize<'de> for SchoolLifeEventKind {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let value = String::deserialize(deserializer)?;
+    Ok(value.as_str().into())
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct SchoolLifeCounters {
+  pub absences: u32,
+  #[serde(rename = "absencesNonJustifiees")]
+  pub unjustified_absences: u32,
+  #[serde(rename = "retards")]
+  pub delays: u32,
+  #[serde(rename = "retardsNonJustifies")]
+  pub unjustified_delays: u32,
+  #[serde(rename = "punitions")]
+  pub punishments: u32,
+  pub sanctions: u32,
+  pub encouragements: u32,
+}
@@ -0,0 +1,103 @@
+use crate::definitions::models::Attachment;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use serde_with::{base64::Base64, serde_as};
+
+/// Profile of teacher and staff accounts.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TeacherProfile {
+  pub email: String,
+  #[serde(rename = "telPortable")]
+  pub phone_number: String,
+  #[serde(rename = "nomEtablissement")]
+  pub school_name: String,
+  #[serde(rename = "idEtablissement")]
+  pub school_id: String,
+  #[serde(rename = "photo")]
+  pub profile_picture_path: String,
+  pub classes: Vec<TeacherClass>,
+  #[serde(rename = "groupes")]
+  pub groups: Vec<TeacherGroup>,
+  /// Any other value of the profile.
+  #[serde(flatten)]
+  pub other: Map<String, Value>,
+}
+
+/// Class taught by a teacher.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TeacherClass {
+  pub id: u32,
+  pub code: String,
+  #[serde(rename = "libelle")]
+  pub label: String,
+  /// Whether the teacher is the head teacher of the class.
+  #[serde(rename = "isPP", default)]
+  pub is_head_teacher: bool,
+}
+
+/// Group of students taught by a teacher, across one or more classes.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TeacherGroup {
+  pub id: u32,
+  pub code: String,
+  #[serde(rename = "libelle")]
+  pub label: String,
+  #[serde(rename = "idClasse", default)]
+  pub class_id: Option<u32>,
+}
+
+/// Homework given by a teacher to one of their classes or groups.
+#[serde_as]
+#[derive(Debug, Clone, Deserialize)]
+pub struct AssignedHomework {
+  #[serde(rename = "idDevoir")]
+  pub id: u32,
+  #[serde(rename = "matiere")]
+  pub subject: String,
+  #[serde(rename = "codeMatiere")]
+  pub subject_code: String,
+  /// Label of the class or group the homework is for.
+  #[serde(rename = "libelleEntite", default)]
+  pub target: String,
+  #[serde(rename = "donneLe")]
+  pub given_on: NaiveDate,
+  #[serde(rename = "pourLe")]
+  pub due_on: NaiveDate,
+  #[serde(rename = "interrogation", default)]
+  pub is_test: bool,
+  /// HTML description of the work to do.
+  #[serde_as(as = "Base64")]
+  #[serde(rename = "contenu", default)]
+  pub content: String,
+  #[serde(default)]
+  pub documents: Vec<Attachment>,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::AssignedHomework;
+  use crate::definitions::api::APIResponseWrap;
+  use chrono::NaiveDate;
+  use std::fs;
+
+  #[test]
+  fn can_read_assigned_homework() {
+    let data = fs::read_to_string("tests/assigned_homework.json")
+      .expect("failed to read json file");
+
+    let parsed: APIResponseWrap<Vec<AssignedHomework>> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    let latin = &parsed.data[0];
+    assert_eq!(latin.target, "LATIN 4EME");
+    assert_eq!(latin.due_on, NaiveDate::from_ymd_opt(2025, 1, 16).unwrap());
+    assert!(latin.content.contains("première déclinaison"));
+    assert!(!latin.is_test);
+
+    let french = &parsed.data[1];
+    assert!(french.is_test);
+    assert_eq!(french.documents[0].name, "chapitre-3.pdf");
+  }
+}
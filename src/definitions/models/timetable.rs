@@ -0,0 +1,68 @@
+use crate::definitions::formats::{date_time, flag};
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Lesson {
+  pub id: i64,
+  #[serde(rename = "matiere")]
+  pub subject: String,
+  #[serde(rename = "codeMatiere")]
+  pub subject_code: String,
+  /// Usually `"COURS"`, can also be `"PERMANENCE"`, `"CONGE"`, ...
+  #[serde(rename = "typeCours")]
+  pub kind: String,
+  #[serde(rename = "start_date", deserialize_with = "date_time")]
+  pub start: NaiveDateTime,
+  #[serde(rename = "end_date", deserialize_with = "date_time")]
+  pub end: NaiveDateTime,
+  #[serde(rename = "color")]
+  pub color_hex: String,
+  #[serde(rename = "prof")]
+  pub teacher: String,
+  #[serde(rename = "salle")]
+  pub room: String,
+  #[serde(rename = "classe", default)]
+  pub class: String,
+  #[serde(rename = "groupe", default)]
+  pub group: String,
+  #[serde(rename = "isAnnule")]
+  pub is_cancelled: bool,
+  #[serde(rename = "isModifie")]
+  pub is_modified: bool,
+  #[serde(rename = "dispensable", default, deserialize_with = "flag")]
+  pub is_exemptable: bool,
+  #[serde(rename = "dispense", default, deserialize_with = "flag")]
+  pub is_exempted: bool,
+  #[serde(rename = "contenuDeSeance", default)]
+  pub has_session_content: bool,
+  #[serde(rename = "devoirAFaire", default)]
+  pub has_homework: bool,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::Lesson;
+  use crate::definitions::api::APIResponseWrap;
+  use chrono::NaiveDate;
+  use std::fs;
+
+  #[test]
+  fn can_read_timetable() {
+    let data = fs::read_to_string("tests/timetable.json")
+      .expect("failed to read json file");
+
+    let parsed: APIResponseWrap<Vec<Lesson>> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    let lessons = parsed.data;
+    let start = NaiveDate::from_ymd_opt(2025, 1, 13)
+      .and_then(|date| date.and_hms_opt(8, 0, 0))
+      .unwrap();
+
+    assert_eq!(lessons[0].start, start);
+    assert!(lessons[0].has_homework && !lessons[0].is_exempted);
+    assert_eq!(lessons[1].group, "3EME EPS G1");
+    assert!(lessons[1].is_cancelled && lessons[1].is_exempted);
+  }
+}
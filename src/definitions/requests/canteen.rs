@@ -0,0 +1,29 @@
+use crate::definitions::models::MealSlot;
+use chrono::NaiveDate;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct ReservationsRequest {
+  #[serde(rename = "dateDebut")]
+  pub start_date: NaiveDate,
+  #[serde(rename = "dateFin")]
+  pub end_date: NaiveDate,
+}
+
+/// Reservation, or cancellation, of a meal.
+#[derive(Debug, Serialize)]
+pub struct ReservationChangeRequest {
+  pub date: NaiveDate,
+  #[serde(rename = "code")]
+  pub slot: MealSlot,
+  #[serde(rename = "reserve")]
+  pub reserved: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MealPassOrderRequest {
+  #[serde(rename = "idArticle")]
+  pub pass_id: u32,
+  #[serde(rename = "quantite")]
+  pub quantity: u32,
+}
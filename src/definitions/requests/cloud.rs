@@ -0,0 +1,24 @@
+use crate::definitions::models::CloudNodeKind;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct CloudFolderRequest {
+  #[serde(rename = "parentNode")]
+  pub parent: CloudNodeRef,
+  #[serde(rename = "libelle")]
+  pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CloudDeleteRequest {
+  #[serde(rename = "tabNodes")]
+  pub nodes: Vec<CloudNodeRef>,
+}
+
+/// Node of the cloud, as EcoleDirecte expects it in requests.
+#[derive(Debug, Serialize)]
+pub struct CloudNodeRef {
+  pub id: String,
+  #[serde(rename = "type")]
+  pub kind: CloudNodeKind,
+}
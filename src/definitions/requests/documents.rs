@@ -0,0 +1,8 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct DocumentsRequest {
+  /// Empty for the current year, otherwise `"YYYY-YYYY"`.
+  #[serde(rename = "archive")]
+  pub school_year: String,
+}
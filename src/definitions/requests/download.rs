@@ -0,0 +1,8 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct DownloadRequest {
+  /// Always `0`, the file is sent as is.
+  #[serde(rename = "forceDownload")]
+  pub force_download: u8,
+}
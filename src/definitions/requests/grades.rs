@@ -0,0 +1,8 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct GradesRequest {
+  /// Empty for the current school year, otherwise `"YYYY-YYYY"`.
+  #[serde(rename = "anneeScolaire")]
+  pub school_year: String,
+}
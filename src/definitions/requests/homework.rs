@@ -0,0 +1,9 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct HomeworkDoneRequest {
+  #[serde(rename = "idDevoirsEffectues")]
+  pub done: Vec<u32>,
+  #[serde(rename = "idDevoirsNonEffectues")]
+  pub not_done: Vec<u32>,
+}
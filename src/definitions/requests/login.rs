@@ -1,9 +1,13 @@
-use crate::definitions::responses::DoubleAuthSolveResponse;
+use crate::{
+  api::REDACTED,
+  definitions::{models::AccountKind, responses::DoubleAuthSolveResponse},
+};
 use serde::Serialize;
 use serde_with::skip_serializing_none;
+use std::fmt;
 
 #[skip_serializing_none]
-#[derive(Debug, Serialize)]
+#[derive(Serialize)]
 pub struct LoginRequest {
   #[serde(rename = "identifiant")]
   pub username: String,
@@ -26,8 +30,26 @@ pub struct LoginRequest {
   pub double_auth: Option<Vec<DoubleAuthSolveResponse>>,
 
   #[serde(rename = "typeCompte")]
-  pub account_type: Option<String>,
+  pub account_type: Option<AccountKind>,
 
   #[serde(rename = "accesstoken")]
   pub access_token: Option<String>,
 }
+
+impl fmt::Debug for LoginRequest {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("LoginRequest")
+      .field("username", &self.username)
+      .field("password", &REDACTED)
+      .field("device_uuid", &self.device_uuid)
+      .field("is_reauth", &self.is_reauth)
+      .field("remember_me", &self.remember_me)
+      .field("double_auth", &self.double_auth)
+      .field("account_type", &self.account_type)
+      .field(
+        "access_token",
+        &self.access_token.as_ref().map(|_| REDACTED),
+      )
+      .finish()
+  }
+}
@@ -0,0 +1,65 @@
+use crate::definitions::models::Contact;
+use serde::Serialize;
+use serde_with::{base64::Base64, serde_as, skip_serializing_none};
+
+#[derive(Debug, Serialize)]
+pub struct MessagesRequest {
+  /// Empty for the current school year, otherwise `"YYYY-YYYY"`.
+  #[serde(rename = "anneeMessages")]
+  pub school_year: String,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize)]
+pub struct MessagesActionRequest {
+  /// `"marquerCommeLu"`, `"marquerCommeNonLu"` or `"deplacer"`.
+  pub action: String,
+  pub ids: Vec<u32>,
+  /// Destination folder, only used when moving messages.
+  #[serde(rename = "idClasseur")]
+  pub folder_id: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContactSearchRequest {
+  #[serde(rename = "recherche")]
+  pub query: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SendMessageRequest {
+  pub message: OutgoingMessage,
+  #[serde(rename = "anneeMessages")]
+  pub school_year: String,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+pub struct OutgoingMessage {
+  #[serde(rename = "groupesDestinataires")]
+  pub recipient_groups: Vec<RecipientGroup>,
+  pub subject: String,
+  /// HTML body of the message.
+  #[serde_as(as = "Base64")]
+  pub content: String,
+  #[serde(rename = "transfertFiles")]
+  pub transferred_files: Vec<u32>,
+  pub files: Vec<u32>,
+  #[serde(rename = "brouillon")]
+  pub is_draft: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecipientGroup {
+  #[serde(rename = "destinataires")]
+  pub recipients: Vec<Recipient>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Recipient {
+  #[serde(flatten)]
+  pub contact: Contact,
+  /// Always `"to"`, copies aren't available to students and parents.
+  #[serde(rename = "to_cc_cci")]
+  pub field: String,
+}
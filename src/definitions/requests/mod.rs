@@ -1,8 +1,17 @@
 use serde::Serialize;
 automod::dir!("src/definitions/requests");
 
+pub use canteen::*;
+pub use cloud::*;
+pub use documents::*;
 pub use double_auth::*;
+pub use download::*;
+pub use grades::*;
+pub use homework::*;
 pub use login::*;
+pub use messages::*;
+pub use teachers::*;
+pub use timetable::*;
 
 #[derive(Debug, Serialize)]
 pub struct EmptyRequest {}
@@ -0,0 +1,10 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct AssignedHomeworkRequest {
+  #[serde(rename = "dateDebut")]
+  pub start_date: NaiveDate,
+  #[serde(rename = "dateFin")]
+  pub end_date: NaiveDate,
+}
@@ -0,0 +1,13 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct TimetableRequest {
+  #[serde(rename = "dateDebut")]
+  pub start_date: NaiveDate,
+  #[serde(rename = "dateFin")]
+  pub end_date: NaiveDate,
+  /// Whether empty slots should be returned as lessons.
+  #[serde(rename = "avecTrous")]
+  pub with_gaps: bool,
+}
@@ -0,0 +1,67 @@
+use crate::definitions::models::{MealPass, MealPassOrder, ReservationDay};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct ReservationsResponse {
+  #[serde(rename = "jours", default)]
+  pub days: Vec<ReservationDay>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MealPassOrdersResponse {
+  /// Passes that can be ordered.
+  #[serde(rename = "articles", default)]
+  pub passes: Vec<MealPass>,
+  /// Orders already made.
+  #[serde(rename = "commandes", default)]
+  pub orders: Vec<MealPassOrder>,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{MealPassOrdersResponse, ReservationsResponse};
+  use crate::definitions::{api::APIResponseWrap, models::MealSlot};
+  use chrono::NaiveDate;
+  use rust_decimal::Decimal;
+  use std::fs;
+
+  #[test]
+  fn can_read_reservations() {
+    let data = fs::read_to_string("tests/reservations.json")
+      .expect("failed to read json file");
+
+    let parsed: APIResponseWrap<ReservationsResponse> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    let days = parsed.data.days;
+    let lunch = &days[0].meals[0];
+    assert_eq!(lunch.slot, MealSlot::Lunch);
+    assert_eq!(lunch.date, days[0].date);
+    assert!(lunch.is_reserved);
+    assert_eq!(days[1].meals[1].date, days[1].date);
+
+    let before = NaiveDate::from_ymd_opt(2025, 1, 9)
+      .and_then(|date| date.and_hms_opt(12, 0, 0))
+      .unwrap();
+    let after = NaiveDate::from_ymd_opt(2025, 1, 11)
+      .and_then(|date| date.and_hms_opt(12, 0, 0))
+      .unwrap();
+
+    assert!(lunch.is_open_at(before));
+    assert!(!lunch.is_open_at(after));
+    assert!(!days[1].meals[0].is_open_at(before));
+  }
+
+  #[test]
+  fn can_read_meal_pass_orders() {
+    let data = fs::read_to_string("tests/meal_pass_orders.json")
+      .expect("failed to read json file");
+
+    let parsed: APIResponseWrap<MealPassOrdersResponse> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    assert_eq!(parsed.data.passes[0].price, Decimal::new(4250, 2));
+    assert_eq!(parsed.data.orders[0].quantity, 1);
+    assert_eq!(parsed.data.orders[0].amount, Decimal::new(4250, 2));
+  }
+}
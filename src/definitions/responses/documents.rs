@@ -0,0 +1,57 @@
+use crate::definitions::models::Document;
+use serde::Deserialize;
+
+/// Documents of a student, by category.
+#[derive(Debug, Deserialize)]
+pub struct DocumentsResponse {
+  /// Report cards (bulletins).
+  #[serde(rename = "notes", default)]
+  pub report_cards: Vec<Document>,
+  /// School certificates and other school life documents.
+  #[serde(rename = "viescolaire", default)]
+  pub certificates: Vec<Document>,
+  #[serde(rename = "administratifs", default)]
+  pub administrative: Vec<Document>,
+  #[serde(rename = "factures", default)]
+  pub invoices: Vec<Document>,
+  #[serde(rename = "inscriptions", default)]
+  pub enrollment: Vec<Document>,
+  /// Internship agreements.
+  #[serde(rename = "entreprises", default)]
+  pub internships: Vec<Document>,
+}
+
+impl DocumentsResponse {
+  /// Every document, whatever its category.
+  pub fn all(&self) -> impl Iterator<Item = &Document> {
+    self
+      .report_cards
+      .iter()
+      .chain(&self.certificates)
+      .chain(&self.administrative)
+      .chain(&self.invoices)
+      .chain(&self.enrollment)
+      .chain(&self.internships)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::DocumentsResponse;
+  use crate::definitions::api::APIResponseWrap;
+  use std::fs;
+
+  #[test]
+  fn can_read_documents() {
+    let data = fs::read_to_string("tests/documents.json")
+      .expect("failed to read json file");
+
+    let parsed: APIResponseWrap<DocumentsResponse> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    let documents = parsed.data;
+    assert_eq!(documents.report_cards.len(), 2);
+    assert_eq!(documents.certificates[0].kind, "Doc");
+    assert_eq!(documents.all().count(), 4);
+  }
+}
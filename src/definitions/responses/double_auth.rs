@@ -1,5 +1,7 @@
+use crate::api::REDACTED;
 use serde::{Deserialize, Serialize};
 use serde_with::{base64::Base64, serde_as};
+use std::fmt;
 
 #[serde_as]
 #[derive(Debug, Deserialize)]
@@ -11,10 +13,42 @@ pub struct DoubleAuthChallengeResponse {
   pub answers: Vec<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+/// Trusted device identifiers given once the double auth is solved,
+/// sending them back on the next logins skips the challenge.
+#[derive(Deserialize, Serialize, Clone, PartialEq)]
 pub struct DoubleAuthSolveResponse {
   #[serde(rename = "cn")]
   name: String,
   #[serde(rename = "cv")]
   value: String,
 }
+
+impl DoubleAuthSolveResponse {
+  pub fn new(name: String, value: String) -> Self {
+    Self { name, value }
+  }
+
+  /// `cn` identifier of the device.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// `cv` secret of the device.
+  pub fn value(&self) -> &str {
+    &self.value
+  }
+
+  /// Identifiers are left empty when the answer is wrong.
+  pub(crate) fn is_empty(&self) -> bool {
+    self.name.is_empty() || self.value.is_empty()
+  }
+}
+
+impl fmt::Debug for DoubleAuthSolveResponse {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("DoubleAuthSolveResponse")
+      .field("name", &REDACTED)
+      .field("value", &REDACTED)
+      .finish()
+  }
+}
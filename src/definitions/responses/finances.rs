@@ -0,0 +1,47 @@
+use crate::definitions::models::{FinancialAccount, Invoice};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct FinancialSituationResponse {
+  #[serde(rename = "comptes", default)]
+  pub accounts: Vec<FinancialAccount>,
+  #[serde(rename = "factures", default)]
+  pub invoices: Vec<Invoice>,
+}
+
+impl FinancialSituationResponse {
+  /// Invoices that haven't been fully paid yet.
+  pub fn outstanding_invoices(&self) -> impl Iterator<Item = &Invoice> {
+    self
+      .invoices
+      .iter()
+      .filter(|invoice| invoice.is_outstanding())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::FinancialSituationResponse;
+  use crate::definitions::api::APIResponseWrap;
+  use rust_decimal::Decimal;
+  use std::fs;
+
+  #[test]
+  fn can_read_financial_situation() {
+    let data = fs::read_to_string("tests/financial_situation.json")
+      .expect("failed to read json file");
+
+    let parsed: APIResponseWrap<FinancialSituationResponse> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    let canteen = &parsed.data.accounts[0];
+    assert_eq!(canteen.balance, Decimal::new(-123450, 2));
+    assert_eq!(canteen.transactions[0].amount, Decimal::new(1, 1));
+    assert_eq!(canteen.transactions[1].amount, Decimal::new(-4230, 2));
+    assert_eq!(parsed.data.accounts[1].balance, Decimal::ZERO);
+
+    let outstanding = parsed.data.outstanding_invoices().collect::<Vec<_>>();
+    assert_eq!(outstanding.len(), 1);
+    assert_eq!(outstanding[0].remaining, Decimal::new(8500, 2));
+  }
+}
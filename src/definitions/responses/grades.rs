@@ -0,0 +1,30 @@
+use crate::definitions::models::{Grade, Period};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct GradesResponse {
+  #[serde(rename = "periodes")]
+  pub periods: Vec<Period>,
+  #[serde(rename = "notes")]
+  pub grades: Vec<Grade>,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::GradesResponse;
+  use crate::definitions::{api::APIResponseWrap, models::GradeValue};
+  use std::fs;
+
+  #[test]
+  fn can_read_grades() {
+    let data = fs::read_to_string("tests/grades.json")
+      .expect("failed to read json file");
+
+    let parsed: APIResponseWrap<GradesResponse> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    let grades = parsed.data.grades;
+    assert_eq!(grades[0].value, GradeValue::Number(15.5));
+    assert_eq!(grades[1].value, GradeValue::Absent);
+  }
+}
@@ -0,0 +1,54 @@
+use crate::definitions::models::UpcomingHomework;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Upcoming homework by due date, from today on.
+#[derive(Debug, Deserialize)]
+#[serde(transparent)]
+pub struct UpcomingHomeworkResponse {
+  pub days: BTreeMap<NaiveDate, Vec<UpcomingHomework>>,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::UpcomingHomeworkResponse;
+  use crate::definitions::{api::APIResponseWrap, models::HomeworkDay};
+  use chrono::NaiveDate;
+  use std::fs;
+
+  #[test]
+  fn can_read_homework_day() {
+    let data = fs::read_to_string("tests/homework_day.json")
+      .expect("failed to read json file");
+
+    let parsed: APIResponseWrap<HomeworkDay> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    let entries = parsed.data.entries;
+    let assignment = entries[0].assignment.as_ref().unwrap();
+    assert_eq!(assignment.content, "<p>Exercices 1 à 3 page 42</p>");
+    assert!(assignment.is_done);
+    assert_eq!(assignment.documents[0].name, "exercices.pdf");
+
+    assert!(entries[1].assignment.is_none());
+    let session = entries[1].session_content.as_ref().unwrap();
+    assert!(session.content.starts_with("<p>Correction du contrôle"));
+  }
+
+  #[test]
+  fn can_read_upcoming_homework() {
+    let data = fs::read_to_string("tests/upcoming_homework.json")
+      .expect("failed to read json file");
+
+    let parsed: APIResponseWrap<UpcomingHomeworkResponse> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    let days = parsed.data.days;
+    let date = NaiveDate::from_ymd_opt(2025, 1, 14).unwrap();
+    assert_eq!(days.len(), 2);
+    assert_eq!(days[&date][0].id, 5412);
+    assert!(!days[&date][0].is_done);
+    assert!(days[&date][1].is_test);
+  }
+}
@@ -1,7 +1,7 @@
 use crate::definitions::models::Account;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct LoginResponse {
   #[serde(rename = "changementMDP")]
   pub password_change: bool,
@@ -13,7 +13,10 @@ pub struct LoginResponse {
 #[cfg(test)]
 mod tests {
   use super::LoginResponse;
-  use crate::definitions::api::APIResponseWrap;
+  use crate::definitions::{
+    api::APIResponseWrap,
+    models::{Account, AccountKind, ModuleKind, ModuleParams, Profile},
+  };
   use std::fs;
 
   #[test]
@@ -48,4 +51,168 @@ mod tests {
 
     println!("{parsed:#?}");
   }
+
+  #[test]
+  fn can_read_account_modules() {
+    let data = fs::read_to_string("tests/student_login.json")
+      .expect("failed to read json file");
+
+    let parsed: APIResponseWrap<LoginResponse> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    let account = &parsed.data.accounts[0];
+    assert!(account.has_module(ModuleKind::Grades));
+    assert!(!account.has_module(ModuleKind::ClassJournal));
+
+    let barcode = account
+      .modules
+      .iter()
+      .find(|module| module.kind == ModuleKind::CanteenBarcode)
+      .expect("missing canteen barcode module");
+
+    match &barcode.params {
+      ModuleParams::CanteenBarcode(params) => {
+        assert_eq!(params.badge_number, "eleve||4179||237629")
+      }
+      params => panic!("unexpected params: {params:?}"),
+    }
+
+    assert_eq!(account.canteen_badge_number(), Some("237629"));
+  }
+
+  #[test]
+  fn accepts_accounts_without_modules() {
+    let data = fs::read_to_string("tests/family_login.json")
+      .expect("failed to read json file");
+
+    let mut json: serde_json::Value = serde_json::from_str(&data).unwrap();
+    let account = &mut json["data"]["accounts"][0];
+    account.as_object_mut().unwrap().remove("modules");
+    account["profile"]["eleves"][0]
+      .as_object_mut()
+      .unwrap()
+      .remove("modules");
+
+    let parsed: APIResponseWrap<LoginResponse> =
+      serde_json::from_value(json).expect("failed to parse json");
+
+    let account = &parsed.data.accounts[0];
+    assert!(account.modules.is_empty());
+    assert!(!account.has_module(ModuleKind::Grades));
+  }
+
+  #[test]
+  fn can_read_individual_parameters() {
+    let data = fs::read_to_string("tests/family_login.json")
+      .expect("failed to read json file");
+
+    let parsed: APIResponseWrap<LoginResponse> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    let parameters = &parsed.data.accounts[0].individual_parameters;
+    assert!(parameters.is_qr_code);
+    assert!(!parameters.visual_accessibility);
+    assert!(parameters.other.is_empty());
+    assert_eq!(parameters.max_homework_submission_days, None);
+
+    // numbers are sent as strings or as numbers depending on the school.
+    let mut json: serde_json::Value = serde_json::from_str(&data).unwrap();
+    let parameters = &mut json["data"]["accounts"][0]["parametresIndividuels"];
+    parameters["nbJoursMaxRenduDevoirCDT"] = 15.into();
+    parameters["lsuPoilDansLaMainBorne1"] = "8".into();
+
+    let parsed: APIResponseWrap<LoginResponse> =
+      serde_json::from_value(json).expect("failed to parse json");
+
+    let parameters = &parsed.data.accounts[0].individual_parameters;
+    assert_eq!(parameters.max_homework_submission_days, Some(15));
+    assert_eq!(parameters.lsu_mastery_bound_1, Some(8));
+    assert_eq!(parameters.lsu_mastery_bound_2, None);
+  }
+
+  #[test]
+  fn reads_profile_by_account_kind() {
+    let data = fs::read_to_string("tests/family_login.json")
+      .expect("failed to read json file");
+
+    let parsed: APIResponseWrap<LoginResponse> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    let account = &parsed.data.accounts[0];
+    assert_eq!(account.kind, AccountKind::Family);
+    assert!(matches!(account.profile, Profile::Parent(_)));
+
+    // a staff account must not be mistaken for another kind.
+    let data = fs::read_to_string("tests/staff_login.json")
+      .expect("failed to read json file");
+
+    let parsed: APIResponseWrap<LoginResponse> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    let account = &parsed.data.accounts[0];
+    assert_eq!(account.kind, AccountKind::Staff);
+    assert!(account.kind.uses_apip());
+    let Profile::Teacher(profile) = &account.profile else {
+      panic!("not a staff profile");
+    };
+    assert_eq!(profile.email, "vie.scolaire@aplim.fr");
+    assert!(profile.classes.is_empty());
+
+    let json = serde_json::to_string(account).unwrap();
+    let account: Account = serde_json::from_str(&json).unwrap();
+    assert_eq!(account.kind.code(), "A");
+  }
+
+  #[test]
+  fn can_read_teacher_profile() {
+    let data = fs::read_to_string("tests/teacher_login.json")
+      .expect("failed to read json file");
+
+    let parsed: APIResponseWrap<LoginResponse> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    let account = &parsed.data.accounts[0];
+    assert_eq!(account.kind, AccountKind::Teacher);
+    let Profile::Teacher(profile) = &account.profile else {
+      panic!("not a teacher profile");
+    };
+
+    assert!(profile.classes[0].is_head_teacher);
+    assert_eq!(profile.groups[0].code, "LAT4");
+    assert_eq!(profile.groups[0].class_id, None);
+    assert_eq!(profile.groups[1].class_id, Some(12));
+  }
+
+  #[test]
+  fn reports_profile_errors_at_their_path() {
+    let data = fs::read_to_string("tests/student_login.json")
+      .expect("failed to read json file");
+
+    // edited as text, to keep `typeCompte` before the profile.
+    let data =
+      data.replace(r#""estApprenant": false"#, r#""estApprenant": "oui""#);
+
+    let deserializer = &mut serde_json::Deserializer::from_str(&data);
+    let parsed: Result<APIResponseWrap<LoginResponse>, _> =
+      serde_path_to_error::deserialize(deserializer);
+    let error = parsed.expect_err("parsed an invalid profile");
+
+    assert_eq!(
+      error.path().to_string(),
+      "data.accounts[0].profile.estApprenant"
+    );
+  }
+
+  #[test]
+  fn can_read_profile_on_its_own() {
+    let data = fs::read_to_string("tests/student_login.json")
+      .expect("failed to read json file");
+
+    let json: serde_json::Value = serde_json::from_str(&data).unwrap();
+    let profile: Profile =
+      serde_json::from_value(json["data"]["accounts"][0]["profile"].clone())
+        .expect("failed to parse profile");
+
+    assert!(matches!(profile, Profile::Student(_)));
+  }
 }
@@ -0,0 +1,42 @@
+use crate::definitions::models::{Contact, Message, MessageFolder};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct MessagesResponse {
+  #[serde(rename = "classeurs", default)]
+  pub folders: Vec<MessageFolder>,
+  pub messages: MessagesByBox,
+  pub pagination: MessagesPagination,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MessagesByBox {
+  #[serde(default)]
+  pub received: Vec<Message>,
+  #[serde(default)]
+  pub sent: Vec<Message>,
+  #[serde(default)]
+  pub archived: Vec<Message>,
+  #[serde(default)]
+  pub draft: Vec<Message>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MessagesPagination {
+  #[serde(rename = "messagesRecusCount", default)]
+  pub received_count: u32,
+  #[serde(rename = "messagesRecusNotReadCount", default)]
+  pub received_unread_count: u32,
+  #[serde(rename = "messagesEnvoyesCount", default)]
+  pub sent_count: u32,
+  #[serde(rename = "messagesArchivesCount", default)]
+  pub archived_count: u32,
+  #[serde(rename = "messagesDraftCount", default)]
+  pub draft_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContactsResponse {
+  #[serde(default)]
+  pub contacts: Vec<Contact>,
+}
@@ -1,4 +1,12 @@
 automod::dir!("src/definitions/responses");
 
+pub use canteen::*;
+pub use documents::*;
 pub use double_auth::*;
+pub use finances::*;
+pub use grades::*;
+pub use homework::*;
 pub use login::*;
+pub use messages::*;
+pub use school_life::*;
+pub use teachers::*;
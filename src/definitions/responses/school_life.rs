@@ -0,0 +1,113 @@
+use crate::definitions::models::{
+  SchoolLifeCounters, SchoolLifeEvent, SchoolLifeEventKind,
+};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct SchoolLifeResponse {
+  #[serde(rename = "absencesRetards", default)]
+  pub absences_and_delays: Vec<SchoolLifeEvent>,
+  #[serde(rename = "sanctionsEncouragements", default)]
+  pub sanctions_and_encouragements: Vec<SchoolLifeEvent>,
+  /// Counters computed by the school, see [`SchoolLifeResponse::counters`].
+  #[serde(rename = "compteurs", default)]
+  pub school_counters: Option<SchoolLifeCounters>,
+  #[serde(rename = "parametrage")]
+  pub settings: Option<SchoolLifeSettings>,
+}
+
+/// What the school decided to show to students and parents.
+#[derive(Debug, Deserialize)]
+pub struct SchoolLifeSettings {
+  #[serde(rename = "justificationEnLigne", default)]
+  pub online_justification: bool,
+  #[serde(rename = "sanctionsVisible", default)]
+  pub sanctions_visible: bool,
+  #[serde(rename = "encouragementsVisible", default)]
+  pub encouragements_visible: bool,
+}
+
+impl SchoolLifeResponse {
+  /// Counters given by the school, or computed from the events
+  /// when the school doesn't send them.
+  pub fn counters(&self) -> SchoolLifeCounters {
+    self
+      .school_counters
+      .clone()
+      .unwrap_or_else(|| self.count_events())
+  }
+
+  /// Count every event by kind.
+  pub fn count_events(&self) -> SchoolLifeCounters {
+    let mut counters = SchoolLifeCounters::default();
+
+    let events = self
+      .absences_and_delays
+      .iter()
+      .chain(&self.sanctions_and_encouragements);
+
+    for event in events {
+      match event.kind {
+        SchoolLifeEventKind::Absence => {
+          counters.absences += 1;
+          counters.unjustified_absences += u32::from(!event.is_justified);
+        }
+        SchoolLifeEventKind::Delay => {
+          counters.delays += 1;
+          counters.unjustified_delays += u32::from(!event.is_justified);
+        }
+        SchoolLifeEventKind::Punishment => counters.punishments += 1,
+        SchoolLifeEventKind::Sanction => counters.sanctions += 1,
+        SchoolLifeEventKind::Encouragement => counters.encouragements += 1,
+        SchoolLifeEventKind::Other(_) => {}
+      }
+    }
+
+    counters
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::SchoolLifeResponse;
+  use crate::definitions::{api::APIResponseWrap, models::SchoolLifeCounters};
+  use std::fs;
+
+  #[test]
+  fn can_read_school_life_counters() {
+    let data = fs::read_to_string("tests/school_life.json")
+      .expect("failed to read json file");
+
+    let mut parsed: APIResponseWrap<SchoolLifeResponse> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    assert_eq!(
+      parsed.data.counters(),
+      SchoolLifeCounters {
+        absences: 3,
+        unjustified_absences: 1,
+        delays: 2,
+        unjustified_delays: 1,
+        punishments: 1,
+        sanctions: 0,
+        encouragements: 1,
+      }
+    );
+
+    // without counters from the school, events are counted.
+    parsed.data.school_counters = None;
+
+    assert_eq!(
+      parsed.data.counters(),
+      SchoolLifeCounters {
+        absences: 1,
+        unjustified_absences: 0,
+        delays: 1,
+        unjustified_delays: 1,
+        punishments: 1,
+        sanctions: 0,
+        encouragements: 1,
+      }
+    );
+  }
+}
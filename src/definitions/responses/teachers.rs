@@ -0,0 +1,36 @@
+use crate::definitions::models::{TeacherClass, TeacherGroup};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct TeacherClassesResponse {
+  #[serde(default)]
+  pub classes: Vec<TeacherClass>,
+  #[serde(rename = "groupes", default)]
+  pub groups: Vec<TeacherGroup>,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::TeacherClassesResponse;
+  use crate::definitions::api::APIResponseWrap;
+  use std::fs;
+
+  #[test]
+  fn can_read_teacher_classes() {
+    let data = fs::read_to_string("tests/teacher_classes.json")
+      .expect("failed to read json file");
+
+    let parsed: APIResponseWrap<TeacherClassesResponse> =
+      serde_json::from_str(&data).expect("failed to parse json");
+
+    let classes = &parsed.data.classes;
+    assert_eq!(classes.len(), 2);
+    assert!(classes[0].is_head_teacher);
+    assert!(!classes[1].is_head_teacher);
+
+    let groups = &parsed.data.groups;
+    assert_eq!(groups[0].label, "LATIN 4EME");
+    assert_eq!(groups[0].class_id, None);
+    assert_eq!(groups[1].class_id, Some(12));
+  }
+}
@@ -1,2 +1,7 @@
 pub mod api;
+#[cfg(feature = "barcode")]
+pub mod barcode;
 pub mod definitions;
+
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
@@ -0,0 +1,583 @@
+//! In-process stand-in for EcoleDirecte replaying the fixtures of `tests/`,
+//! so logins can be tested end to end without network nor real accounts.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! use pawdirecte::{api::LoginManager, mock};
+//!
+//! let server = mock::MockServer::start().await?;
+//!
+//! let mut login = LoginManager::from_credentials(
+//!   mock::STUDENT_USERNAME.into(),
+//!   mock::PASSWORD.into(),
+//!   None,
+//! )
+//! .with_http_config(&server.http_config())?;
+//!
+//! login.request().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+  api::{Error, HttpConfig, LoginManager},
+  definitions::models::AccountKind,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use serde_with::{base64::Base64, serde_as};
+use std::{
+  collections::HashMap,
+  net::SocketAddr,
+  sync::{Arc, Mutex},
+};
+use tokio::{
+  io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+  net::{TcpListener, TcpStream},
+  task::JoinHandle,
+};
+
+/// Password of every mock account.
+pub const PASSWORD: &str = "pawdirecte";
+/// Student account, replays `tests/student_login.json`.
+pub const STUDENT_USERNAME: &str = "EDELEVE";
+/// Primary school student account, replays `tests/studentp_login.json`.
+pub const PRIMARY_STUDENT_USERNAME: &str = "EDELEVEP";
+/// Parent account, replays `tests/family_login.json`.
+pub const FAMILY_USERNAME: &str = "EDFAMILLE";
+/// Student account that has to solve a double authentication challenge
+/// before replaying `tests/student_login.json`.
+pub const DOUBLE_AUTH_USERNAME: &str = "EDELEVE2FA";
+pub const DOUBLE_AUTH_QUESTION: &str = "Quelle est votre année de naissance ?";
+pub const DOUBLE_AUTH_ANSWERS: [&str; 3] = ["2009", "2010", "2011"];
+pub const DOUBLE_AUTH_ANSWER: &str = "2010";
+/// Teacher account, replays `tests/teacher_login.json`, only known by
+/// the APIP host like every teacher and staff account.
+pub const TEACHER_USERNAME: &str = "EDPROF";
+/// Staff account, replays `tests/staff_login.json`.
+pub const STAFF_USERNAME: &str = "EDPERSONNEL";
+/// Account always answered with code 517, the app version is outdated.
+pub const OUTDATED_USERNAME: &str = "EDOUTDATED";
+/// Account always answered with code 535, the establishment is closed.
+pub const CLOSED_USERNAME: &str = "EDCLOSED";
+
+const GTK: &str = "mock-gtk";
+const TRUSTED_DEVICE_NAME: &str = "mock-cn";
+const TRUSTED_DEVICE_VALUE: &str = "mock-cv";
+
+const STUDENT_LOGIN: &str = include_str!("../tests/student_login.json");
+const PRIMARY_STUDENT_LOGIN: &str =
+  include_str!("../tests/studentp_login.json");
+const FAMILY_LOGIN: &str = include_str!("../tests/family_login.json");
+const TEACHER_LOGIN: &str = include_str!("../tests/teacher_login.json");
+const STAFF_LOGIN: &str = include_str!("../tests/staff_login.json");
+const DOUBLE_AUTH_LOGIN: &str = include_str!("../tests/2fa_login_init.json");
+const MESSAGES: &str = include_str!("../tests/messages.json");
+const MESSAGE: &str = include_str!("../tests/message.json");
+const SCHOOL_LIFE: &str = include_str!("../tests/school_life.json");
+const TEACHER_CLASSES: &str = include_str!("../tests/teacher_classes.json");
+const TEACHER_TIMETABLE: &str = include_str!("../tests/teacher_timetable.json");
+const ASSIGNED_HOMEWORK: &str = include_str!("../tests/assigned_homework.json");
+const UPCOMING_HOMEWORK: &str = include_str!("../tests/upcoming_homework.json");
+const HOMEWORK_DAY: &str = include_str!("../tests/homework_day.json");
+const RESERVATIONS: &str = include_str!("../tests/reservations.json");
+const MEAL_PASS_ORDERS: &str = include_str!("../tests/meal_pass_orders.json");
+const DOCUMENTS: &str = include_str!("../tests/documents.json");
+const FINANCIAL_SITUATION: &str =
+  include_str!("../tests/financial_situation.json");
+
+/// Content of every file downloaded from the mock.
+pub const FILE_CONTENT: &str = "%PDF-1.4 pawdirecte mock file";
+
+/// Access token given to `username` on every successful login.
+pub fn access_token(username: &str) -> String {
+  format!("mock-access-token-{username}")
+}
+
+/// Mock server listening on two random local ports, one for each host
+/// of EcoleDirecte, it stops as soon as it's dropped.
+#[derive(Debug)]
+pub struct MockServer {
+  address: SocketAddr,
+  apip_address: SocketAddr,
+  state: Arc<Mutex<State>>,
+  tasks: [JoinHandle<()>; 2],
+}
+
+impl MockServer {
+  pub async fn start() -> std::io::Result<Self> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let apip_listener = TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+    let apip_address = apip_listener.local_addr()?;
+    let state = Arc::new(Mutex::new(State::default()));
+
+    Ok(Self {
+      address,
+      apip_address,
+      tasks: [
+        tokio::spawn(listen(listener, Host::Api, state.clone())),
+        tokio::spawn(listen(apip_listener, Host::Apip, state.clone())),
+      ],
+      state,
+    })
+  }
+
+  /// Stands for [`API_URL`](crate::api::API_URL).
+  pub fn url(&self) -> String {
+    format!("http://{}", self.address)
+  }
+
+  /// Stands for [`APIP_URL`](crate::api::APIP_URL).
+  pub fn apip_url(&self) -> String {
+    format!("http://{}", self.apip_address)
+  }
+
+  /// Config sending every request of a login to this server.
+  pub fn http_config(&self) -> HttpConfig {
+    HttpConfig::new()
+      .api_url(self.url())
+      .apip_url(self.apip_url())
+  }
+
+  /// Log in to one of the mock accounts with [`PASSWORD`].
+  pub async fn login(&self, username: &str) -> Result<LoginManager, Error> {
+    let mut login =
+      LoginManager::from_credentials(username.into(), PASSWORD.into(), None)
+        .with_http_config(&self.http_config())?;
+
+    login = match username {
+      TEACHER_USERNAME => login.with_account_kind(AccountKind::Teacher),
+      STAFF_USERNAME => login.with_account_kind(AccountKind::Staff),
+      _ => login,
+    };
+
+    login.request().await?;
+    Ok(login)
+  }
+
+  /// Make every token issued so far expire, the next data request
+  /// is answered with code 525.
+  pub fn expire_tokens(&self) {
+    let mut state = self.state.lock().unwrap();
+    state.expired = state.tokens.len();
+  }
+
+  /// Every request received so far, oldest first.
+  pub fn received(&self) -> Vec<ReceivedRequest> {
+    self.state.lock().unwrap().received.clone()
+  }
+}
+
+impl Drop for MockServer {
+  fn drop(&mut self) {
+    for task in &self.tasks {
+      task.abort();
+    }
+  }
+}
+
+/// Host of EcoleDirecte a request has been sent to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Host {
+  Api,
+  /// Host of teachers and staff members.
+  Apip,
+}
+
+async fn listen(listener: TcpListener, host: Host, state: Arc<Mutex<State>>) {
+  while let Ok((stream, _)) = listener.accept().await {
+    tokio::spawn(serve(stream, host, state.clone()));
+  }
+}
+
+#[derive(Debug, Default)]
+struct State {
+  tokens: Vec<String>,
+  /// Tokens issued before this index have expired.
+  expired: usize,
+  received: Vec<ReceivedRequest>,
+}
+
+impl State {
+  fn issue_token(&mut self) -> String {
+    let token = format!("mock-token-{}", self.tokens.len());
+    self.tokens.push(token.clone());
+    token
+  }
+
+  fn is_valid_token(&self, token: Option<&String>) -> bool {
+    token.is_some_and(|token| self.tokens[self.expired..].contains(token))
+  }
+}
+
+/// Request as received by the mock, to check what has been sent.
+#[derive(Debug, Clone)]
+pub struct ReceivedRequest {
+  pub host: Host,
+  pub method: String,
+  pub path: String,
+  pub query: HashMap<String, String>,
+  pub headers: HashMap<String, String>,
+  pub body: String,
+}
+
+impl ReceivedRequest {
+  /// JSON payload sent as the `data` field of the form.
+  pub fn form<T: serde::de::DeserializeOwned>(&self) -> Option<T> {
+    let form: HashMap<String, String> =
+      serde_urlencoded::from_str(&self.body).ok()?;
+
+    serde_json::from_str(form.get("data")?).ok()
+  }
+}
+
+struct HttpResponse {
+  content_type: &'static str,
+  headers: Vec<(&'static str, String)>,
+  body: String,
+}
+
+impl HttpResponse {
+  fn api(code: u32, token: &str, message: &str, data: Value) -> Self {
+    let body = json!({
+      "code": code,
+      "token": token,
+      "message": message,
+      "data": data,
+    });
+
+    Self::json(body.to_string())
+  }
+
+  fn json(body: String) -> Self {
+    Self {
+      content_type: "application/json",
+      headers: vec![],
+      body,
+    }
+  }
+
+  fn into_bytes(self) -> Vec<u8> {
+    let mut response = format!(
+      "HTTP/1.1 200 OK\r\ncontent-type: {}\r\ncontent-length: {}\r\nconnection: close\r\n",
+      self.content_type,
+      self.body.len()
+    );
+
+    for (name, value) in self.headers {
+      response.push_str(&format!("{name}: {value}\r\n"));
+    }
+
+    response.push_str("\r\n");
+    response.push_str(&self.body);
+    response.into_bytes()
+  }
+}
+
+async fn serve(
+  stream: TcpStream,
+  host: Host,
+  state: Arc<Mutex<State>>,
+) -> std::io::Result<()> {
+  let mut reader = BufReader::new(stream);
+
+  let mut line = String::new();
+  reader.read_line(&mut line).await?;
+
+  let mut request_line = line.split_whitespace();
+  let method = request_line.next().unwrap_or_default().to_string();
+  let target = request_line.next().unwrap_or_default().to_string();
+
+  let mut headers = HashMap::new();
+
+  loop {
+    line.clear();
+    reader.read_line(&mut line).await?;
+
+    let Some((name, value)) = line.trim_end().split_once(':') else {
+      break;
+    };
+
+    headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+  }
+
+  let length = headers
+    .get("content-length")
+    .and_then(|length| length.parse().ok())
+    .unwrap_or(0);
+
+  let mut body = vec![0; length];
+  reader.read_exact(&mut body).await?;
+
+  let (path, query) = target.split_once('?').unwrap_or((target.as_str(), ""));
+
+  let request = ReceivedRequest {
+    host,
+    method,
+    path: path.into(),
+    query: serde_urlencoded::from_str(query).unwrap_or_default(),
+    headers,
+    body: String::from_utf8_lossy(&body).into(),
+  };
+
+  let response = {
+    let mut state = state.lock().unwrap();
+    state.received.push(request.clone());
+    handle(&request, &mut state)
+  };
+
+  let mut stream = reader.into_inner();
+  stream.write_all(&response.into_bytes()).await?;
+  stream.shutdown().await
+}
+
+fn handle(request: &ReceivedRequest, state: &mut State) -> HttpResponse {
+  match (request.method.as_str(), request.path.as_str()) {
+    ("GET", "/v3/login.awp") => gtk(),
+    ("POST", "/v3/login.awp") => login(request, state),
+    ("POST", "/v3/connexion/doubleauth.awp") => double_auth(request, state),
+    ("POST", _) => data(request, state),
+    _ => HttpResponse::api(404, "", "Not found", json!({})),
+  }
+}
+
+/// Data endpoints, answered with the fixtures of `tests/`.
+fn data(request: &ReceivedRequest, state: &mut State) -> HttpResponse {
+  if !state.is_valid_token(request.headers.get("x-token")) {
+    return HttpResponse::api(525, "", "Token invalide !", json!({}));
+  }
+
+  let token = state.issue_token();
+  let verb = request.query.get("verbe").map(String::as_str);
+  let segments = request.path.split('/').skip(1).collect::<Vec<_>>();
+
+  let fixture = match (verb, segments.as_slice()) {
+    (Some("get"), ["v3", _, _, "messages.awp"]) => MESSAGES,
+    (Some("get"), ["v3", _, _, "messages", _]) => MESSAGE,
+    (Some("get"), ["v3", "eleves", _, "viescolaire.awp"]) => SCHOOL_LIFE,
+    (Some("get"), ["v3", "eleves", _, "cahierdetexte.awp"]) => {
+      UPCOMING_HOMEWORK
+    }
+    (Some("get"), ["v3", "eleves", _, "cahierdetexte", day]) => {
+      return homework_day(day.trim_end_matches(".awp"), &token);
+    }
+    (Some("get"), ["v3", "eleves", _, "reservations.awp"]) => RESERVATIONS,
+    (Some("get"), ["v3", "eleves", _, "commandesPassage.awp"]) => {
+      MEAL_PASS_ORDERS
+    }
+    (Some("post"), ["v3", "eleves", _, "commandesPassage.awp"]) => {
+      let json: Value = serde_json::from_str(MEAL_PASS_ORDERS)
+        .expect("fixtures are valid json");
+
+      let order = json["data"]["commandes"][0].clone();
+      return HttpResponse::api(200, &token, "", order);
+    }
+    (Some("get"), ["v3", "elevesDocuments.awp"]) => DOCUMENTS,
+    (Some("get"), ["v3", "comptes", "detail.awp"]) => FINANCIAL_SITUATION,
+    (Some("get"), ["v3", "enseignants" | "personnels", _, "classes.awp"])
+      if request.host == Host::Apip =>
+    {
+      TEACHER_CLASSES
+    }
+    (
+      Some("get"),
+      ["v3", "enseignants" | "personnels", _, "emploidutemps.awp"],
+    ) if request.host == Host::Apip => TEACHER_TIMETABLE,
+    (
+      Some("get"),
+      ["v3", "enseignants" | "personnels", _, "cahierdetexte.awp"],
+    ) if request.host == Host::Apip => ASSIGNED_HOMEWORK,
+    (Some("put" | "post"), ["v3", _, _, "messages.awp"])
+    | (Some("put"), ["v3", "eleves", _, "reservations.awp"])
+    | (Some("put"), ["v3", "eleves", _, "cahierdetexte.awp"])
+    | (Some("post"), ["v3", "televersement.awp"])
+    | (Some("delete"), ["v3", "cloud", _, _]) => {
+      return HttpResponse::api(200, &token, "", json!({}));
+    }
+    (Some("get"), ["v3", "telechargement.awp"]) => return download(request),
+    _ => return HttpResponse::api(404, &token, "Not found", json!({})),
+  };
+
+  let mut json: Value =
+    serde_json::from_str(fixture).expect("fixtures are valid json");
+  json["token"] = token.into();
+
+  HttpResponse::json(json.to_string())
+}
+
+/// Only the day of `tests/homework_day.json` has something planned,
+/// every other day is empty.
+fn homework_day(date: &str, token: &str) -> HttpResponse {
+  let json: Value =
+    serde_json::from_str(HOMEWORK_DAY).expect("fixtures are valid json");
+
+  if json["data"]["date"] == date {
+    return HttpResponse::api(200, token, "", json["data"].clone());
+  }
+
+  HttpResponse::api(200, token, "", json!({ "date": date, "matieres": [] }))
+}
+
+/// Any file is answered with [`FILE_CONTENT`], named after the last
+/// component of its id.
+fn download(request: &ReceivedRequest) -> HttpResponse {
+  let file_id = request.query.get("fichierId").cloned().unwrap_or_default();
+
+  HttpResponse {
+    content_type: "application/octet-stream",
+    headers: vec![(
+      "content-disposition",
+      format!("attachment; filename=\"{file_id}\""),
+    )],
+    body: FILE_CONTENT.into(),
+  }
+}
+
+fn gtk() -> HttpResponse {
+  let mut response = HttpResponse::api(200, "", "", json!({}));
+  response.headers = vec![
+    ("set-cookie", format!("GTK={GTK}; Path=/; HttpOnly")),
+    ("set-cookie", "mock-session=1; Path=/".into()),
+  ];
+
+  response
+}
+
+#[derive(Deserialize)]
+struct LoginForm {
+  #[serde(rename = "identifiant")]
+  username: String,
+  #[serde(rename = "motdepasse")]
+  password: String,
+  #[serde(rename = "isReLogin")]
+  is_reauth: bool,
+  #[serde(rename = "accesstoken")]
+  access_token: Option<String>,
+  #[serde(rename = "fa", default)]
+  double_auth: Vec<TrustedDevice>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct TrustedDevice {
+  cn: String,
+  cv: String,
+}
+
+fn login(request: &ReceivedRequest, state: &mut State) -> HttpResponse {
+  let invalid = |message| HttpResponse::api(505, "", message, json!({}));
+
+  if request.headers.get("x-gtk").map(String::as_str) != Some(GTK) {
+    return invalid("GTK manquant !");
+  }
+
+  let Some(form) = request.form::<LoginForm>() else {
+    return invalid("Formulaire invalide !");
+  };
+
+  let is_authorized = if form.is_reauth {
+    form.access_token == Some(access_token(&form.username))
+  } else {
+    form.password == PASSWORD
+  };
+
+  if !is_authorized {
+    return invalid("Identifiant et/ou mot de passe invalide !");
+  }
+
+  let is_trusted_device = form.double_auth.iter().any(|device| {
+    device.cn == TRUSTED_DEVICE_NAME && device.cv == TRUSTED_DEVICE_VALUE
+  });
+
+  match form.username.as_str() {
+    OUTDATED_USERNAME => HttpResponse::api(
+      517,
+      "",
+      "Une nouvelle version d'EcoleDirecte est disponible.",
+      json!({}),
+    ),
+    CLOSED_USERNAME => {
+      HttpResponse::api(535, "", "L'établissement est fermé.", json!({}))
+    }
+    STUDENT_USERNAME => replay(STUDENT_LOGIN, &form.username, state),
+    PRIMARY_STUDENT_USERNAME => {
+      replay(PRIMARY_STUDENT_LOGIN, &form.username, state)
+    }
+    FAMILY_USERNAME => replay(FAMILY_LOGIN, &form.username, state),
+    TEACHER_USERNAME if request.host == Host::Apip => {
+      replay(TEACHER_LOGIN, &form.username, state)
+    }
+    STAFF_USERNAME if request.host == Host::Apip => {
+      replay(STAFF_LOGIN, &form.username, state)
+    }
+    DOUBLE_AUTH_USERNAME if form.is_reauth || is_trusted_device => {
+      replay(STUDENT_LOGIN, &form.username, state)
+    }
+    DOUBLE_AUTH_USERNAME => replay(DOUBLE_AUTH_LOGIN, &form.username, state),
+    _ => invalid("Identifiant et/ou mot de passe invalide !"),
+  }
+}
+
+/// Send a fixture back with a fresh token and the mock access token.
+fn replay(fixture: &str, username: &str, state: &mut State) -> HttpResponse {
+  let mut json: Value =
+    serde_json::from_str(fixture).expect("fixtures are valid json");
+
+  json["token"] = state.issue_token().into();
+
+  if let Some(accounts) = json["data"]["accounts"].as_array_mut() {
+    for account in accounts {
+      account["accessToken"] = access_token(username).into();
+    }
+  }
+
+  HttpResponse::json(json.to_string())
+}
+
+#[serde_as]
+#[derive(Serialize)]
+struct Challenge {
+  #[serde_as(as = "Base64")]
+  question: String,
+  #[serde_as(as = "Vec<Base64>")]
+  propositions: Vec<String>,
+}
+
+#[serde_as]
+#[derive(Deserialize)]
+struct Answer {
+  #[serde_as(as = "Base64")]
+  choix: String,
+}
+
+fn double_auth(request: &ReceivedRequest, state: &mut State) -> HttpResponse {
+  if !state.is_valid_token(request.headers.get("x-token")) {
+    return HttpResponse::api(525, "", "Token invalide !", json!({}));
+  }
+
+  let token = state.issue_token();
+
+  match request.query.get("verbe").map(String::as_str) {
+    Some("get") => {
+      let challenge = Challenge {
+        question: DOUBLE_AUTH_QUESTION.into(),
+        propositions: DOUBLE_AUTH_ANSWERS.map(String::from).to_vec(),
+      };
+
+      HttpResponse::api(200, &token, "", json!(challenge))
+    }
+    Some("post") => match request.form::<Answer>() {
+      Some(answer) if answer.choix == DOUBLE_AUTH_ANSWER => {
+        let device = TrustedDevice {
+          cn: TRUSTED_DEVICE_NAME.into(),
+          cv: TRUSTED_DEVICE_VALUE.into(),
+        };
+
+        HttpResponse::api(200, &token, "", json!(device))
+      }
+      _ => HttpResponse::api(202, &token, "Réponse incorrecte !", json!({})),
+    },
+    _ => HttpResponse::api(404, &token, "Not found", json!({})),
+  }
+}